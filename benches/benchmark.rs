@@ -2,41 +2,118 @@
 #[allow(unused_imports)]
 use criterion::{black_box, criterion_group, criterion_main, Criterion};
 
+use library::{Direction, Part, Point2, Point3, PointSet, RawInput, VoxelGrid};
+use std::collections::HashSet;
+
 // Load library module.
-#[path = "../src/library.rs"]
+#[path = "../src/library/mod.rs"]
+#[allow(dead_code)]
 mod library;
 
 // Load the solutions folder mod.rs
 #[path = "../src/solutions/mod.rs"]
+#[allow(dead_code)]
 mod solutions;
 
-criterion_group!(
-    benches,
-    solutions::day01::benchmark,
-    solutions::day02::benchmark,
-    solutions::day03::benchmark,
-    solutions::day04::benchmark,
-    solutions::day05::benchmark,
-    solutions::day06::benchmark,
-    solutions::day07::benchmark,
-    solutions::day08::benchmark,
-    solutions::day09::benchmark,
-    solutions::day10::benchmark,
-    solutions::day11::benchmark,
-    solutions::day12::benchmark,
-    solutions::day13::benchmark,
-    solutions::day14::benchmark,
-    solutions::day15::benchmark,
-    solutions::day16::benchmark,
-    solutions::day17::benchmark,
-    solutions::day18::benchmark,
-    solutions::day19::benchmark,
-    solutions::day20::benchmark,
-    solutions::day21::benchmark,
-    solutions::day22::benchmark,
-    solutions::day23::benchmark,
-    solutions::day24::benchmark,
-    solutions::day25::benchmark
-);
+// Benchmark every registered day, both with a pre-parsed input and parsing from scratch.
+// The file is only read once, so parsing is measured without the disk or allocating lines.
+fn benchmark(c: &mut Criterion) {
+    for day in solutions::SOLUTIONS {
+        let raw = RawInput::load(&library::input_path(day.number())).unwrap();
+        let input = day.parse_input(&raw).unwrap();
+
+        c.bench_function(&format!("Day {:02} - solve:", day.number()), |b| {
+            b.iter(|| day.solve_input(&*input, Part::Both))
+        });
+        c.bench_function(&format!("Day {:02} - parse & solve:", day.number()), |b| {
+            b.iter(|| {
+                let input = day.parse_input(&raw).unwrap();
+                day.solve_input(&*input, Part::Both)
+            })
+        });
+    }
+}
+
+// The bitset-backed PointSet against the HashSet it replaced, on a wandering walk that keeps checking the cells around it.
+// That's what the rope, sand and elf simulations spend their time doing, and days 09, 14, 18 and 23 are timed on each as well.
+fn point_sets(c: &mut Criterion) {
+    // A fixed pseudo-random walk, so both sets get exactly the same work.
+    let mut seed = 12345u64;
+    let mut p = Point2::ORIGIN;
+    let walk = (0..100_000)
+        .map(|_| {
+            seed = seed
+                .wrapping_mul(6364136223846793005)
+                .wrapping_add(1442695040888963407);
+            p = p.step(Direction::ALL[(seed >> 62) as usize]);
+            p
+        })
+        .collect::<Vec<_>>();
+
+    let mut group = c.benchmark_group("Point sets");
+    group.bench_function("HashSet", |b| {
+        b.iter(|| {
+            let mut set = HashSet::new();
+            let mut crowded = 0;
+            for &p in &walk {
+                set.insert(p);
+                crowded += p.neighbours8().iter().filter(|n| set.contains(n)).count();
+            }
+            black_box(crowded)
+        })
+    });
+    group.bench_function("PointSet", |b| {
+        b.iter(|| {
+            let mut set = PointSet::new();
+            let mut crowded = 0;
+            for &p in &walk {
+                set.insert(p);
+                crowded += p.neighbours8().iter().filter(|n| set.contains(n)).count();
+            }
+            black_box(crowded)
+        })
+    });
+    group.finish();
+
+    // Then the days that were ported over, each on its real input, with the same solve run on both kinds of set.
+    let input = |day| RawInput::load(&library::input_path(day)).unwrap();
+
+    let moves = solutions::day09::parse(&input(9)).unwrap();
+    let mut group = c.benchmark_group("Point sets - Day 09");
+    group.bench_function("HashSet", |b| {
+        b.iter(|| solutions::day09::solve_with::<HashSet<Point2>>(&moves))
+    });
+    group.bench_function("PointSet", |b| {
+        b.iter(|| solutions::day09::solve_with::<PointSet>(&moves))
+    });
+    group.finish();
+
+    let rocks = solutions::day14::parse(&input(14)).unwrap();
+    let hashed = rocks.iter().collect::<HashSet<_>>();
+    let mut group = c.benchmark_group("Point sets - Day 14");
+    group.bench_function("HashSet", |b| b.iter(|| solutions::day14::solve(&hashed)));
+    group.bench_function("PointSet", |b| b.iter(|| solutions::day14::solve(&rocks)));
+    group.finish();
+
+    let droplet = solutions::day18::parse(&input(18)).unwrap();
+    let hashed = VoxelGrid::<HashSet<Point3>>::from_points(droplet.iter());
+    let mut group = c.benchmark_group("Point sets - Day 18");
+    group.bench_function("HashSet", |b| b.iter(|| solutions::day18::solve(&hashed)));
+    group.bench_function("CubeSet", |b| b.iter(|| solutions::day18::solve(&droplet)));
+    group.finish();
+
+    // The elves move around in the set they're given, so each run starts from a fresh copy.
+    let elves = solutions::day23::parse(&input(23));
+    let hashed = elves.iter().collect::<HashSet<_>>();
+    let mut group = c.benchmark_group("Point sets - Day 23");
+    group.bench_function("HashSet", |b| {
+        b.iter(|| solutions::day23::solve(&mut hashed.clone()))
+    });
+    group.bench_function("PointSet", |b| {
+        b.iter(|| solutions::day23::solve(&mut elves.clone()))
+    });
+    group.finish();
+}
 
+criterion_group!(benches, benchmark, point_sets);
 criterion_main!(benches);
@@ -0,0 +1,392 @@
+// Command line handling for the solution runner.
+
+use crate::library::Part;
+use crate::solutions;
+use std::time::Duration;
+
+pub const USAGE: &str = "\
+Usage: fruitcake [DAYS] [OPTIONS]
+       fruitcake verify [DAYS] [--part <1|2>] [--timeout <SECS>] [--data <DIR>] [--record]
+       fruitcake new <DAY> [--year <YEAR>]
+
+Runs the Advent of Code solutions. With no DAYS, every registered day is run.
+`verify` checks the answers against the confirmed ones in data/answers.json,
+and exits non-zero if any changed, panicked or haven't been recorded yet.
+The answers for the examples in testdata are checked with --data testdata.
+`new` creates src/solutions/dayNN.rs from src/template.rs, an empty
+testdata/dayNN.txt, and registers the day. It won't overwrite anything.
+
+Arguments:
+  [DAYS]  Comma-separated days and ranges to run, e.g. 1-5,12,20-25
+
+Options:
+  -p, --part <1|2>     Only solve the given part of each day
+  -i, --input <PATH>   Read the input from PATH instead of data/dayXX.txt (- reads stdin).
+                       Repeat it, or give a directory or glob, to compare several inputs
+  -f, --format <FMT>   Output format: text (default), json or csv
+  -j, --jobs <N>       Run up to N days at once. Output still comes out in day order
+  -t, --time           Print how long each day took to parse and solve
+      --timeout <SECS> Give up on a day that takes longer than SECS seconds
+  -y, --year <YEAR>    Puzzle year to run (only 2022 is available for now)
+  -d, --data <DIR>     With verify, read the inputs and answers.json from DIR instead of data
+  -r, --record         With verify, save the current answers as the confirmed ones
+  -h, --help           Print this help
+";
+
+// The only year solved so far, and the default for --year.
+const YEAR: u32 = 2022;
+
+// Everything needed to run a batch of days, as asked for on the command line.
+#[derive(Debug, PartialEq, Eq)]
+pub struct Options {
+    pub days: Vec<u32>,
+    pub part: Part,
+    pub year: u32,
+    // Inputs to use instead of the day's data file.
+    pub inputs: Vec<String>,
+    // Print a timing summary at the end.
+    pub time: bool,
+    pub format: Format,
+    // How long each day gets before it's reported as timed out.
+    pub timeout: Option<Duration>,
+    // Run days in parallel on this many threads, instead of one after another.
+    pub jobs: Option<usize>,
+}
+
+// How results get printed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Format {
+    Text,
+    Json,
+    Csv,
+}
+
+// What the command line asked for.
+#[derive(Debug, PartialEq, Eq)]
+pub enum Command {
+    Run(Options),
+    // Check the answers against the answer store in `data`, or record them into it.
+    Verify {
+        options: Options,
+        data: String,
+        record: bool,
+    },
+    // Scaffold a new day from the template.
+    New {
+        day: u32,
+        year: u32,
+    },
+    Help,
+}
+
+// Parse the arguments (without the program name) into a command, or an error message explaining what's wrong with them.
+pub fn parse(args: &[String]) -> Result<Command, String> {
+    if args.first().is_some_and(|a| a == "new") {
+        return parse_new(&args[1..]);
+    }
+
+    let mut days = None;
+    let mut part = Part::Both;
+    let mut year = YEAR;
+    let mut inputs = Vec::new();
+    let mut time = false;
+    let mut format = Format::Text;
+    let mut record = false;
+    let mut data = "data".to_string();
+    let mut timeout = None;
+    let mut jobs = None;
+
+    // A leading `verify` switches to checking answers instead of printing them.
+    let verify = args.first().is_some_and(|a| a == "verify");
+    let mut args = args[verify as usize..].iter();
+    while let Some(arg) = args.next() {
+        // Allow both `--flag value` and `--flag=value`.
+        let (flag, inline) = match arg.split_once('=') {
+            Some((flag, value)) if arg.starts_with("--") => (flag, Some(value.to_string())),
+            _ => (arg.as_str(), None),
+        };
+        let mut value = |name: &str| {
+            inline
+                .clone()
+                .or_else(|| args.next().cloned())
+                .ok_or(format!("Missing value for {name}"))
+        };
+
+        match flag {
+            "-h" | "--help" => return Ok(Command::Help),
+            // Verify only reports whether the answers still match, so it has no output formats, timings or jobs to honour.
+            "-f" | "--format" | "-t" | "--time" | "-j" | "--jobs" if verify => {
+                return Err(format!("{flag} can't be used with verify"))
+            }
+            "-p" | "--part" => {
+                part = match value("--part")?.as_str() {
+                    "1" => Part::One,
+                    "2" => Part::Two,
+                    other => return Err(format!("Invalid part '{other}', expected 1 or 2")),
+                }
+            }
+            "-i" | "--input" => inputs.push(value("--input")?),
+            "-t" | "--time" => time = true,
+            "-r" | "--record" if verify => record = true,
+            "-d" | "--data" if verify => data = value("--data")?,
+            "-j" | "--jobs" => {
+                let v = value("--jobs")?;
+                let n = v
+                    .parse::<usize>()
+                    .ok()
+                    .filter(|&n| n > 0)
+                    .ok_or(format!("Invalid job count '{v}'"))?;
+                jobs = Some(n);
+            }
+            "--timeout" => {
+                let v = value("--timeout")?;
+                let secs = v
+                    .parse::<f64>()
+                    .ok()
+                    .filter(|s| *s > 0.0 && s.is_finite())
+                    .ok_or(format!(
+                        "Invalid timeout '{v}', expected a number of seconds"
+                    ))?;
+                timeout = Some(Duration::from_secs_f64(secs));
+            }
+            "-f" | "--format" => {
+                format = match value("--format")?.as_str() {
+                    "text" => Format::Text,
+                    "json" => Format::Json,
+                    "csv" => Format::Csv,
+                    other => return Err(format!("Unknown format '{other}'")),
+                }
+            }
+            "-y" | "--year" => year = parse_year(&value("--year")?)?,
+            _ if flag.starts_with('-') => return Err(format!("Unknown option '{flag}'")),
+            _ if days.is_some() => return Err(format!("Unexpected argument '{arg}'")),
+            _ => days = Some(parse_days(arg)?),
+        }
+    }
+
+    // Inputs are for a specific day, so they only make sense when running a single day.
+    if !inputs.is_empty() && !matches!(days.as_deref(), Some([_])) {
+        return Err("--input needs exactly one day to run".to_string());
+    }
+
+    // The answer store only has answers for each day's own data file.
+    if verify && !inputs.is_empty() {
+        return Err("--input can't be used with verify".to_string());
+    }
+
+    let days = days.unwrap_or_else(|| solutions::SOLUTIONS.iter().map(|d| d.number()).collect());
+    let options = Options {
+        days,
+        part,
+        year,
+        inputs,
+        time,
+        format,
+        timeout,
+        jobs,
+    };
+    Ok(match verify {
+        true => Command::Verify {
+            options,
+            data,
+            record,
+        },
+        false => Command::Run(options),
+    })
+}
+
+// Arguments for `new`, which only takes the day and the year.
+fn parse_new(args: &[String]) -> Result<Command, String> {
+    let mut day = None;
+    let mut year = YEAR;
+
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        let (flag, inline) = match arg.split_once('=') {
+            Some((flag, value)) if arg.starts_with("--") => (flag, Some(value.to_string())),
+            _ => (arg.as_str(), None),
+        };
+        match flag {
+            "-h" | "--help" => return Ok(Command::Help),
+            "-y" | "--year" => {
+                let v = inline
+                    .or_else(|| args.next().cloned())
+                    .ok_or("Missing value for --year")?;
+                year = parse_year(&v)?;
+            }
+            _ if flag.starts_with('-') => return Err(format!("Unknown option '{flag}'")),
+            _ if day.is_some() => return Err(format!("Unexpected argument '{arg}'")),
+            _ => match arg.parse::<u32>() {
+                Ok(d @ 1..=25) => day = Some(d),
+                _ => return Err(format!("Invalid day '{arg}', expected 1 to 25")),
+            },
+        }
+    }
+
+    let day = day.ok_or("new needs the day to create")?;
+    Ok(Command::New { day, year })
+}
+
+fn parse_year(v: &str) -> Result<u32, String> {
+    let year = v.parse().map_err(|_| format!("Invalid year '{v}'"))?;
+    if year != YEAR {
+        return Err(format!("No solutions for {year}, only {YEAR} is available"));
+    }
+    Ok(year)
+}
+
+// Parse a day selection such as "1-5,12,20-25" into a sorted list of registered days.
+pub fn parse_days(spec: &str) -> Result<Vec<u32>, String> {
+    // The old wildcard still means every day.
+    if spec == "*" || spec == "all" {
+        return Ok(solutions::SOLUTIONS.iter().map(|d| d.number()).collect());
+    }
+
+    let mut days = Vec::new();
+    for item in spec.split(',') {
+        let (start, end) = item.split_once('-').unwrap_or((item, item));
+        let (start, end) = (parse_day(start)?, parse_day(end)?);
+        if start > end {
+            return Err(format!("Invalid range '{item}', start is after end"));
+        }
+        days.extend(start..=end);
+    }
+
+    days.sort();
+    days.dedup();
+    Ok(days)
+}
+
+fn parse_day(s: &str) -> Result<u32, String> {
+    let day = s
+        .trim()
+        .parse::<u32>()
+        .map_err(|_| format!("Invalid day '{s}'"))?;
+    match solutions::find(day) {
+        Some(_) => Ok(day),
+        None => Err(format!("Day {day} has no solution")),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #[allow(unused_imports)]
+    use super::*;
+
+    fn args(s: &str) -> Vec<String> {
+        s.split_whitespace().map(String::from).collect()
+    }
+
+    #[test]
+    fn day_ranges() {
+        let days = parse_days("1-5,12,20-25,3").unwrap();
+        assert_eq!(days, vec![1, 2, 3, 4, 5, 12, 20, 21, 22, 23, 24, 25]);
+        assert!(parse_days("5-1").is_err());
+        assert!(parse_days("26").is_err());
+        assert!(parse_days("1,x").is_err());
+    }
+
+    #[test]
+    fn options() {
+        let expected = Command::Run(Options {
+            days: vec![2, 3],
+            part: Part::Two,
+            year: 2022,
+            inputs: vec![],
+            time: false,
+            format: Format::Text,
+            timeout: None,
+            jobs: None,
+        });
+        assert_eq!(parse(&args("2-3 --part 2")).unwrap(), expected);
+        assert_eq!(parse(&args("--year=2022 -p 2 2,3")).unwrap(), expected);
+        assert_eq!(parse(&args("1 --help")).unwrap(), Command::Help);
+        assert!(parse(&args("1 --part 3")).is_err());
+        assert!(parse(&args("1 --year 2021")).is_err());
+        assert!(parse(&args("1 2")).is_err());
+        assert!(parse(&args("1 --format xml")).is_err());
+        assert!(parse(&args("1 --timeout 0")).is_err());
+        assert!(parse(&args("1 --jobs 0")).is_err());
+        assert!(parse(&args("--verbose")).is_err());
+    }
+
+    #[test]
+    fn input() {
+        let expected = Command::Run(Options {
+            days: vec![15],
+            part: Part::Both,
+            year: 2022,
+            inputs: vec!["-".to_string()],
+            time: false,
+            format: Format::Text,
+            timeout: None,
+            jobs: None,
+        });
+        assert_eq!(parse(&args("15 --input -")).unwrap(), expected);
+
+        let expected = Command::Run(Options {
+            days: vec![15],
+            part: Part::Both,
+            year: 2022,
+            inputs: vec!["a.txt".to_string(), "inputs/".to_string()],
+            time: true,
+            format: Format::Csv,
+            timeout: Some(Duration::from_millis(2500)),
+            jobs: Some(4),
+        });
+        assert_eq!(
+            parse(&args(
+                "15 -i a.txt --input=inputs/ --time -f csv --timeout 2.5 -j 4"
+            ))
+            .unwrap(),
+            expected
+        );
+        assert!(parse(&args("--input testdata/day15.txt")).is_err());
+        assert!(parse(&args("1-2 -i testdata/day01.txt")).is_err());
+    }
+
+    #[test]
+    fn verify() {
+        let Ok(Command::Verify {
+            options,
+            data,
+            record,
+        }) = parse(&args("verify 1-3 --record --data testdata"))
+        else {
+            panic!("expected a verify command");
+        };
+        assert_eq!(options.days, vec![1, 2, 3]);
+        assert_eq!(data, "testdata");
+        assert!(record);
+
+        let Ok(Command::Verify {
+            options,
+            data,
+            record,
+        }) = parse(&args("verify"))
+        else {
+            panic!("expected a verify command");
+        };
+        assert_eq!(options.days.len(), solutions::SOLUTIONS.len());
+        assert_eq!(data, "data");
+        assert!(!record);
+
+        assert!(parse(&args("1 --record")).is_err());
+        assert!(parse(&args("1 --data testdata")).is_err());
+        assert!(parse(&args("verify 1 -i testdata/day01.txt")).is_err());
+        assert!(parse(&args("verify --format json")).is_err());
+        assert!(parse(&args("verify -j 4")).is_err());
+        assert!(parse(&args("verify --time")).is_err());
+    }
+
+    #[test]
+    fn new() {
+        assert_eq!(
+            parse(&args("new 7 --year 2022")).unwrap(),
+            Command::New { day: 7, year: 2022 }
+        );
+        assert!(parse(&args("new")).is_err());
+        assert!(parse(&args("new 26")).is_err());
+        assert!(parse(&args("new 7 --part 1")).is_err());
+    }
+}
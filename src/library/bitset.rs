@@ -0,0 +1,443 @@
+// Sets of points stored as one bit per cell of a box around them, for simulations that check the same handful of cells millions of times.
+// Works like a HashSet of points, but a lookup is some arithmetic and a bit test instead of hashing. The box grows to fit whatever is
+// inserted, or it can be sized up front when the bounds are known. A point so far from the rest that the box would get huge is kept
+// in a plain HashSet on the side instead, so a couple of distant points can't ask for terabytes of bits.
+
+use super::{Point2, Point3};
+use std::collections::HashSet;
+use std::hash::Hash;
+use std::marker::PhantomData;
+
+// The most cells a box can cover, which is 32MiB of bits.
+const MAX_CELLS: i64 = 1 << 28;
+
+// A plain set of small numbers, one bit each.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct BitSet {
+    words: Vec<u64>,
+    len: usize,
+}
+
+impl BitSet {
+    // Room for the numbers below `bits` without growing.
+    pub fn with_capacity(bits: usize) -> Self {
+        BitSet {
+            words: vec![0; bits.div_ceil(64)],
+            len: 0,
+        }
+    }
+
+    // Returns whether the number was newly added, like HashSet::insert.
+    pub fn insert(&mut self, n: usize) -> bool {
+        let (word, bit) = (n / 64, 1 << (n % 64));
+        if word >= self.words.len() {
+            self.words.resize(word + 1, 0);
+        }
+        let added = self.words[word] & bit == 0;
+        self.words[word] |= bit;
+        self.len += added as usize;
+        added
+    }
+
+    pub fn remove(&mut self, n: usize) -> bool {
+        let (word, bit) = (n / 64, 1 << (n % 64));
+        let removed = self.words.get(word).is_some_and(|w| w & bit != 0);
+        if removed {
+            self.words[word] &= !bit;
+            self.len -= 1;
+        }
+        removed
+    }
+
+    pub fn contains(&self, n: usize) -> bool {
+        self.words
+            .get(n / 64)
+            .is_some_and(|w| w & (1 << (n % 64)) != 0)
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    pub fn clear(&mut self) {
+        self.words.fill(0);
+        self.len = 0;
+    }
+
+    // The numbers in the set, smallest first. Skips a whole word at a time when it's empty.
+    pub fn iter(&self) -> impl Iterator<Item = usize> + '_ {
+        self.words.iter().enumerate().flat_map(|(i, &word)| {
+            let mut word = word;
+            std::iter::from_fn(move || {
+                if word == 0 {
+                    return None;
+                }
+                let bit = word.trailing_zeros() as usize;
+                word &= word - 1;
+                Some(i * 64 + bit)
+            })
+        })
+    }
+}
+
+// A point that can be taken apart into N coordinates and put back together.
+pub trait Coords<const N: usize>: Copy {
+    fn to_axes(self) -> [i64; N];
+    fn from_axes(axes: [i64; N]) -> Self;
+}
+
+impl Coords<2> for Point2 {
+    fn to_axes(self) -> [i64; 2] {
+        [self.x, self.y]
+    }
+
+    fn from_axes([x, y]: [i64; 2]) -> Self {
+        Point2::new(x, y)
+    }
+}
+
+impl Coords<3> for Point3 {
+    fn to_axes(self) -> [i64; 3] {
+        [self.x, self.y, self.z]
+    }
+
+    fn from_axes([x, y, z]: [i64; 3]) -> Self {
+        Point3::new(x, y, z)
+    }
+}
+
+// A set of points, as bits in a box that starts at `min` and is `size` cells long on each axis.
+#[derive(Debug, Clone)]
+pub struct CoordSet<P, const N: usize> {
+    min: [i64; N],
+    size: [i64; N],
+    bits: BitSet,
+    // Points that didn't fit in the box without making it too big. None of them are ever inside the box.
+    far: HashSet<[i64; N]>,
+    point: PhantomData<P>,
+}
+
+pub type PointSet = CoordSet<Point2, 2>;
+pub type CubeSet = CoordSet<Point3, 3>;
+
+impl<P: Coords<N>, const N: usize> CoordSet<P, N> {
+    // An empty set, which makes room as points are added.
+    pub fn new() -> Self {
+        CoordSet {
+            min: [0; N],
+            size: [0; N],
+            bits: BitSet::default(),
+            far: HashSet::new(),
+            point: PhantomData,
+        }
+    }
+
+    // An empty set with room for everything from `min` to `max`, inclusive. It still grows if something lands outside.
+    // Panics if the box would be bigger than MAX_CELLS.
+    pub fn with_bounds(min: P, max: P) -> Self {
+        let (min, max) = (min.to_axes(), max.to_axes());
+        let size = std::array::from_fn(|a| (max[a] - min[a] + 1).max(0));
+        let cells = volume(size).unwrap_or_else(|| {
+            panic!("a box from {min:?} to {max:?} has more than {MAX_CELLS} cells")
+        });
+        CoordSet {
+            min,
+            size,
+            bits: BitSet::with_capacity(cells as usize),
+            far: HashSet::new(),
+            point: PhantomData,
+        }
+    }
+
+    // Where a point's bit is, or None if it's outside the box.
+    fn index(&self, p: P) -> Option<usize> {
+        let mut index = 0;
+        for (a, axis) in p.to_axes().into_iter().enumerate() {
+            let offset = axis - self.min[a];
+            if offset < 0 || offset >= self.size[a] {
+                return None;
+            }
+            index = index * self.size[a] + offset;
+        }
+        Some(index as usize)
+    }
+
+    // The point whose bit is at `index`. The last axis changes fastest.
+    fn point(&self, mut index: usize) -> P {
+        let mut axes = [0; N];
+        for a in (0..N).rev() {
+            let size = self.size[a] as usize;
+            axes[a] = self.min[a] + (index % size) as i64;
+            index /= size;
+        }
+        P::from_axes(axes)
+    }
+
+    // Make the box big enough for `p`, unless that would make it too big, returning whether it did. Each axis at least doubles
+    // on the side it grows, so a walk heading off in one direction only has to copy everything over a few times.
+    fn grow(&mut self, p: P) -> bool {
+        let mut min = self.min;
+        let mut size = self.size;
+        for (a, axis) in p.to_axes().into_iter().enumerate() {
+            let slack = self.size[a].max(8);
+            if self.size[a] == 0 {
+                min[a] = axis - slack / 2;
+                size[a] = slack;
+            } else if axis < self.min[a] {
+                min[a] = axis - slack;
+                size[a] = self.min[a] + self.size[a] - min[a];
+            } else if axis >= self.min[a] + self.size[a] {
+                size[a] = axis + slack - min[a];
+            }
+        }
+
+        if volume(size).is_none() {
+            return false;
+        }
+
+        let mut grown = CoordSet::with_bounds(
+            P::from_axes(min),
+            P::from_axes(std::array::from_fn(|a| min[a] + size[a] - 1)),
+        );
+        // The far points might fit in the bigger box now, so they go in again too.
+        for q in self.iter() {
+            grown.insert(q);
+        }
+        *self = grown;
+        true
+    }
+
+    // Returns whether the point was newly added, like HashSet::insert.
+    pub fn insert(&mut self, p: P) -> bool {
+        if self.index(p).is_none() && !self.grow(p) {
+            return self.far.insert(p.to_axes());
+        }
+        let i = self.index(p).expect("the set grew to fit the point");
+        self.bits.insert(i)
+    }
+
+    pub fn remove(&mut self, p: &P) -> bool {
+        match self.index(*p) {
+            Some(i) => self.bits.remove(i),
+            None => self.far.remove(&p.to_axes()),
+        }
+    }
+
+    pub fn contains(&self, p: &P) -> bool {
+        match self.index(*p) {
+            Some(i) => self.bits.contains(i),
+            None => self.far.contains(&p.to_axes()),
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.bits.len() + self.far.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    pub fn clear(&mut self) {
+        self.bits.clear();
+        self.far.clear();
+    }
+
+    // The points in the set. Those in the box come first, ordered by their first axis, then the next, and so on.
+    pub fn iter(&self) -> impl Iterator<Item = P> + '_ {
+        self.bits
+            .iter()
+            .map(|i| self.point(i))
+            .chain(self.far.iter().map(|&axes| P::from_axes(axes)))
+    }
+}
+
+// How many cells a box of this size covers, or None if that's more than MAX_CELLS.
+fn volume<const N: usize>(size: [i64; N]) -> Option<i64> {
+    size.iter()
+        .try_fold(1i64, |cells, &s| cells.checked_mul(s))
+        .filter(|&cells| cells <= MAX_CELLS)
+}
+
+// What the simulations need from a set of points. Both CoordSet and HashSet have it, so a day written against it
+// can be run, and benched, on either.
+pub trait Points<P>: Clone + Default + FromIterator<P> {
+    // An empty set with room for everything from `min` to `max`, for sets that can make use of knowing that.
+    fn with_bounds(_min: P, _max: P) -> Self {
+        Self::default()
+    }
+
+    fn insert(&mut self, p: P) -> bool;
+    fn remove(&mut self, p: &P) -> bool;
+    fn contains(&self, p: &P) -> bool;
+    fn len(&self) -> usize;
+    fn iter(&self) -> impl Iterator<Item = P> + '_;
+
+    fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+impl<P: Coords<N>, const N: usize> Points<P> for CoordSet<P, N> {
+    fn with_bounds(min: P, max: P) -> Self {
+        CoordSet::with_bounds(min, max)
+    }
+
+    fn insert(&mut self, p: P) -> bool {
+        CoordSet::insert(self, p)
+    }
+
+    fn remove(&mut self, p: &P) -> bool {
+        CoordSet::remove(self, p)
+    }
+
+    fn contains(&self, p: &P) -> bool {
+        CoordSet::contains(self, p)
+    }
+
+    fn len(&self) -> usize {
+        CoordSet::len(self)
+    }
+
+    fn iter(&self) -> impl Iterator<Item = P> + '_ {
+        CoordSet::iter(self)
+    }
+}
+
+impl<P: Copy + Eq + Hash> Points<P> for HashSet<P> {
+    fn insert(&mut self, p: P) -> bool {
+        HashSet::insert(self, p)
+    }
+
+    fn remove(&mut self, p: &P) -> bool {
+        HashSet::remove(self, p)
+    }
+
+    fn contains(&self, p: &P) -> bool {
+        HashSet::contains(self, p)
+    }
+
+    fn len(&self) -> usize {
+        HashSet::len(self)
+    }
+
+    fn iter(&self) -> impl Iterator<Item = P> + '_ {
+        HashSet::iter(self).copied()
+    }
+}
+
+impl<P: Coords<N>, const N: usize> Default for CoordSet<P, N> {
+    fn default() -> Self {
+        CoordSet::new()
+    }
+}
+
+// Equal when they hold the same points, whatever size their boxes are.
+impl<P: Coords<N>, const N: usize> PartialEq for CoordSet<P, N> {
+    fn eq(&self, other: &Self) -> bool {
+        self.len() == other.len() && self.iter().all(|p| other.contains(&p))
+    }
+}
+
+impl<P: Coords<N>, const N: usize> Eq for CoordSet<P, N> {}
+
+impl<P: Coords<N>, const N: usize> Extend<P> for CoordSet<P, N> {
+    fn extend<I: IntoIterator<Item = P>>(&mut self, iter: I) {
+        for p in iter {
+            self.insert(p);
+        }
+    }
+}
+
+// Collecting sizes the box to fit every point first, so it never has to grow. If that box would be too big, the points go in one by one instead.
+impl<P: Coords<N>, const N: usize> FromIterator<P> for CoordSet<P, N> {
+    fn from_iter<I: IntoIterator<Item = P>>(iter: I) -> Self {
+        let points = iter.into_iter().collect::<Vec<_>>();
+        let Some(first) = points.first() else {
+            return CoordSet::new();
+        };
+
+        let (mut min, mut max) = (first.to_axes(), first.to_axes());
+        for p in &points {
+            for (a, axis) in p.to_axes().into_iter().enumerate() {
+                min[a] = min[a].min(axis);
+                max[a] = max[a].max(axis);
+            }
+        }
+
+        let mut set = match volume::<N>(std::array::from_fn(|a| max[a] - min[a] + 1)) {
+            Some(_) => CoordSet::with_bounds(P::from_axes(min), P::from_axes(max)),
+            None => CoordSet::new(),
+        };
+        set.extend(points);
+        set
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #[allow(unused_imports)]
+    use super::*;
+
+    #[test]
+    fn bits() {
+        let mut set = BitSet::with_capacity(10);
+        assert!(set.insert(3));
+        assert!(!set.insert(3));
+        assert!(set.insert(200));
+        assert!(set.contains(200) && !set.contains(199) && !set.contains(10_000));
+        assert_eq!(set.iter().collect::<Vec<_>>(), vec![3, 200]);
+        assert!(set.remove(3));
+        assert!(!set.remove(3));
+        assert_eq!(set.len(), 1);
+    }
+
+    #[test]
+    fn grows_to_fit() {
+        // A spiral heading out in every direction, so the box has to grow on every side.
+        let mut set = PointSet::new();
+        let mut hashed = std::collections::HashSet::new();
+        let mut p = Point2::ORIGIN;
+        for (i, dir) in (0..200)
+            .flat_map(|i| std::iter::repeat_n(i, i / 2 + 1))
+            .enumerate()
+        {
+            p = p.step(crate::library::Direction::ALL[dir % 4]);
+            assert_eq!(set.insert(p), hashed.insert(p), "step {i}");
+        }
+        assert_eq!(set.len(), hashed.len());
+        assert!(hashed.iter().all(|p| set.contains(p)));
+        assert!(set.iter().all(|p| hashed.contains(&p)));
+        assert!(!set.contains(&Point2::new(1000, 1000)));
+
+        let cubes = [Point3::new(1, 1, 1), Point3::new(-3, 2, 5)]
+            .into_iter()
+            .collect::<CubeSet>();
+        let mut grown = CubeSet::new();
+        grown.extend([Point3::new(-3, 2, 5), Point3::new(1, 1, 1)]);
+        assert_eq!(cubes, grown);
+        assert_eq!(cubes.iter().next(), Some(Point3::new(-3, 2, 5)));
+
+        // Points this far apart would need a box of 10^12 cells, so the far one is kept on the side until the box can reach it.
+        let mut spread = [Point2::new(0, 0), Point2::new(1_000_000, 1_000_000)]
+            .into_iter()
+            .collect::<PointSet>();
+        assert!(spread.bits.words.len() < 1000 && spread.far.len() == 1);
+        assert!(spread.contains(&Point2::new(1_000_000, 1_000_000)));
+        assert!(!spread.insert(Point2::new(1_000_000, 1_000_000)));
+        assert!(spread.insert(Point2::new(1_000_000, 999_999)));
+        assert_eq!(spread.len(), 3);
+        assert!(spread.remove(&Point2::new(1_000_000, 1_000_000)));
+        assert_eq!(spread.iter().count(), 2);
+
+        let mut bounded = PointSet::with_bounds(Point2::new(0, 0), Point2::new(3, 3));
+        assert!(bounded.insert(Point2::new(3, 3)));
+        assert!(bounded.remove(&Point2::new(3, 3)));
+        assert!(!bounded.remove(&Point2::new(-1, 0)));
+        assert!(bounded.is_empty());
+    }
+}
@@ -0,0 +1,228 @@
+// Finding where a simulation starts repeating itself, so a puzzle that asks about the trillionth step can skip the laps in between.
+// A simulation is an initial state and a step function. States are compared by a key, which only has to hold what decides the future,
+// and steps are counted from the initial state, which is step 0.
+
+use std::collections::hash_map::Entry;
+use std::collections::HashMap;
+use std::hash::Hash;
+
+// The states from `start` on repeat every `length` steps.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Cycle {
+    pub start: usize,
+    pub length: usize,
+}
+
+// How to look for the cycle.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Strategy {
+    // Remember the key of every state until one comes back. Steps through the states once, but keeps every key.
+    Hashed,
+    // Brent's algorithm. Only keeps a couple of states around, but steps through them a few times.
+    Brent,
+}
+
+impl Cycle {
+    // The earliest step whose state is the same as the one at step `n`.
+    pub fn reduce(&self, n: usize) -> usize {
+        match n.checked_sub(self.start) {
+            Some(offset) => self.start + offset % self.length,
+            None => n,
+        }
+    }
+}
+
+// Find the cycle with the given strategy. Never returns if the states don't repeat.
+pub fn find<S, K>(
+    initial: &S,
+    step: impl FnMut(&mut S),
+    key: impl FnMut(&S) -> K,
+    strategy: Strategy,
+) -> Cycle
+where
+    S: Clone,
+    K: Hash + Eq,
+{
+    match strategy {
+        Strategy::Hashed => hashed(initial, step, key),
+        Strategy::Brent => brent(initial, step, key),
+    }
+}
+
+pub fn hashed<S, K>(
+    initial: &S,
+    mut step: impl FnMut(&mut S),
+    mut key: impl FnMut(&S) -> K,
+) -> Cycle
+where
+    S: Clone,
+    K: Hash + Eq,
+{
+    let mut state = initial.clone();
+    let mut seen = HashMap::new();
+
+    for i in 0.. {
+        match seen.entry(key(&state)) {
+            Entry::Occupied(first) => {
+                return Cycle {
+                    start: *first.get(),
+                    length: i - first.get(),
+                }
+            }
+            Entry::Vacant(entry) => {
+                entry.insert(i);
+            }
+        }
+        step(&mut state);
+    }
+
+    unreachable!("the steps run out before the states repeat")
+}
+
+pub fn brent<S, K>(initial: &S, mut step: impl FnMut(&mut S), mut key: impl FnMut(&S) -> K) -> Cycle
+where
+    S: Clone,
+    K: Eq,
+{
+    // Find the length first. The hare runs ahead, and the tortoise jumps to it whenever the distance between them reaches a power of two.
+    // Once the hare is inside the cycle and the power is at least the cycle's length, the hare comes back round to the tortoise.
+    let mut tortoise = key(initial);
+    let mut hare = initial.clone();
+    step(&mut hare);
+    let (mut power, mut length) = (1, 1);
+
+    while key(&hare) != tortoise {
+        if power == length {
+            tortoise = key(&hare);
+            power *= 2;
+            length = 0;
+        }
+        step(&mut hare);
+        length += 1;
+    }
+
+    // Then the start. With the hare a whole cycle ahead, the two of them meet where the cycle begins.
+    let mut tortoise = initial.clone();
+    let mut hare = initial.clone();
+    for _ in 0..length {
+        step(&mut hare);
+    }
+    let mut start = 0;
+
+    while key(&tortoise) != key(&hare) {
+        step(&mut tortoise);
+        step(&mut hare);
+        start += 1;
+    }
+
+    Cycle { start, length }
+}
+
+// A metric that adds up as the simulation runs, like the height of a tower, known for the first lap of the cycle.
+// That's enough to work it out for any step, since each lap adds the same amount.
+#[derive(Debug, Clone)]
+pub struct History {
+    pub cycle: Cycle,
+    // The metric at each step up to the end of the first lap.
+    metrics: Vec<i64>,
+}
+
+impl History {
+    // The metric at step `n`.
+    pub fn at(&self, n: usize) -> i64 {
+        if let Some(&metric) = self.metrics.get(n) {
+            return metric;
+        }
+
+        let Cycle { start, length } = self.cycle;
+        let laps = ((n - start) / length) as i64;
+        let per_lap = self.metrics[start + length] - self.metrics[start];
+        self.metrics[self.cycle.reduce(n)] + laps * per_lap
+    }
+}
+
+// Find the cycle, then run the simulation up to the end of its first lap to record the metric.
+pub fn history<S, K>(
+    initial: &S,
+    mut step: impl FnMut(&mut S),
+    key: impl FnMut(&S) -> K,
+    mut metric: impl FnMut(&S) -> i64,
+    strategy: Strategy,
+) -> History
+where
+    S: Clone,
+    K: Hash + Eq,
+{
+    let cycle = find(initial, &mut step, key, strategy);
+
+    let mut state = initial.clone();
+    let mut metrics = vec![metric(&state)];
+    for _ in 0..cycle.start + cycle.length {
+        step(&mut state);
+        metrics.push(metric(&state));
+    }
+
+    History { cycle, metrics }
+}
+
+#[cfg(test)]
+mod tests {
+    #[allow(unused_imports)]
+    use super::*;
+
+    // A value that wanders around before settling into a loop, and the running total of the values so far.
+    fn step(state: &mut (u64, i64)) {
+        state.0 = (state.0 * state.0 + 1) % 1009;
+        state.1 += state.0 as i64;
+    }
+
+    #[test]
+    fn strategies_agree() {
+        let initial = (3, 0);
+        let hashed = find(&initial, step, |s| s.0, Strategy::Hashed);
+        let brent = find(&initial, step, |s| s.0, Strategy::Brent);
+        assert_eq!(hashed, brent);
+
+        // Check it against stepping one at a time.
+        let mut states = vec![initial];
+        for i in 0..hashed.start + hashed.length {
+            let mut next = states[i];
+            step(&mut next);
+            states.push(next);
+        }
+        let Cycle { start, length } = hashed;
+        assert_eq!(states[start].0, states[start + length].0);
+        assert!((0..start + length)
+            .all(|i| (i + 1..start + length).all(|j| states[i].0 != states[j].0)));
+    }
+
+    #[test]
+    fn extrapolate_against_brute_force() {
+        let initial = (3, 0);
+        let mut state = initial;
+        let mut totals = vec![state.1];
+        for _ in 0..5000 {
+            step(&mut state);
+            totals.push(state.1);
+        }
+
+        for strategy in [Strategy::Hashed, Strategy::Brent] {
+            let history = history(&initial, step, |s| s.0, |s| s.1, strategy);
+            assert!(totals
+                .iter()
+                .enumerate()
+                .all(|(n, &total)| history.at(n) == total));
+        }
+    }
+
+    #[test]
+    fn reduce() {
+        let cycle = Cycle {
+            start: 3,
+            length: 5,
+        };
+        assert_eq!(cycle.reduce(2), 2);
+        assert_eq!(cycle.reduce(8), 3);
+        assert_eq!(cycle.reduce(1_000_000_000_000), 5);
+    }
+}
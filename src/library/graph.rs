@@ -0,0 +1,294 @@
+// Shortest paths over graphs given as closures, so a grid, a network of valves or a whole state space can all be searched the same way.
+// Nodes can be anything that hashes, including (position, time) pairs for puzzles where the map changes as time goes on.
+
+use std::cmp::Reverse;
+use std::collections::hash_map::Entry;
+use std::collections::{BinaryHeap, HashMap, VecDeque};
+use std::hash::Hash;
+use std::ops::Add;
+
+// A path from the start to a goal, including both ends, and what it cost. For BFS the cost is the number of steps.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Path<N, C> {
+    pub nodes: Vec<N>,
+    pub cost: C,
+}
+
+// Walk back through each node's parent to get the path that reached `end`.
+fn unwind<N: Clone>(nodes: &[(N, usize)], end: usize) -> Vec<N> {
+    let mut path = vec![nodes[end].0.clone()];
+    let mut i = end;
+    while nodes[i].1 != i {
+        i = nodes[i].1;
+        path.push(nodes[i].0.clone());
+    }
+    path.reverse();
+    path
+}
+
+// Breadth first search from `start` to the nearest node that satisfies `goal`, where every step costs 1.
+pub fn bfs<N, I>(
+    start: N,
+    mut neighbours: impl FnMut(&N) -> I,
+    mut goal: impl FnMut(&N) -> bool,
+) -> Option<Path<N, usize>>
+where
+    N: Clone + Eq + Hash,
+    I: IntoIterator<Item = N>,
+{
+    // Every node found so far with the index of the node it was reached from, and how far it is from the start.
+    let mut nodes = vec![(start.clone(), 0)];
+    let mut seen = HashMap::from([(start, 0)]);
+    let mut queue = VecDeque::from([(0, 0)]);
+
+    while let Some((i, steps)) = queue.pop_front() {
+        if goal(&nodes[i].0) {
+            return Some(Path {
+                nodes: unwind(&nodes, i),
+                cost: steps,
+            });
+        }
+        for next in neighbours(&nodes[i].0) {
+            if let Entry::Vacant(entry) = seen.entry(next.clone()) {
+                entry.insert(nodes.len());
+                queue.push_back((nodes.len(), steps + 1));
+                nodes.push((next, i));
+            }
+        }
+    }
+
+    None
+}
+
+// The number of steps from `start` to every node it can reach.
+pub fn bfs_distances<N, I>(start: N, mut neighbours: impl FnMut(&N) -> I) -> HashMap<N, usize>
+where
+    N: Clone + Eq + Hash,
+    I: IntoIterator<Item = N>,
+{
+    let mut distances = HashMap::from([(start.clone(), 0)]);
+    let mut queue = VecDeque::from([(start, 0)]);
+
+    while let Some((node, steps)) = queue.pop_front() {
+        for next in neighbours(&node) {
+            if let Entry::Vacant(entry) = distances.entry(next.clone()) {
+                entry.insert(steps + 1);
+                queue.push_back((next, steps + 1));
+            }
+        }
+    }
+
+    distances
+}
+
+// Dijkstra's algorithm: the cheapest path from `start` to a node that satisfies `goal`, where `neighbours` gives each
+// next node with the cost of getting there. Costs can't be negative. Default is taken as a cost of zero.
+pub fn dijkstra<N, C, I>(
+    start: N,
+    neighbours: impl FnMut(&N) -> I,
+    goal: impl FnMut(&N) -> bool,
+) -> Option<Path<N, C>>
+where
+    N: Clone + Eq + Hash,
+    C: Copy + Ord + Default + Add<Output = C>,
+    I: IntoIterator<Item = (N, C)>,
+{
+    astar(start, neighbours, |_| C::default(), goal)
+}
+
+// A*: like Dijkstra, but `heuristic` guesses the cost left to a goal so the search heads that way first.
+// The guess must never be more than the real cost, or the path found might not be the cheapest.
+pub fn astar<N, C, I>(
+    start: N,
+    mut neighbours: impl FnMut(&N) -> I,
+    mut heuristic: impl FnMut(&N) -> C,
+    mut goal: impl FnMut(&N) -> bool,
+) -> Option<Path<N, C>>
+where
+    N: Clone + Eq + Hash,
+    C: Copy + Ord + Default + Add<Output = C>,
+    I: IntoIterator<Item = (N, C)>,
+{
+    // Every node found so far with the index of the node it was reached from, and the cheapest known cost to each.
+    let mut nodes = vec![(start.clone(), 0)];
+    let mut costs = vec![C::default()];
+    let mut index = HashMap::from([(start.clone(), 0)]);
+    let mut queue = BinaryHeap::from([(Reverse(heuristic(&start)), C::default(), 0)]);
+
+    while let Some((_, cost, i)) = queue.pop() {
+        // Nodes are queued again whenever a cheaper way to them turns up, so skip the old, more expensive entries.
+        if cost > costs[i] {
+            continue;
+        }
+        if goal(&nodes[i].0) {
+            return Some(Path {
+                nodes: unwind(&nodes, i),
+                cost,
+            });
+        }
+
+        for (next, step) in neighbours(&nodes[i].0) {
+            let next_cost = cost + step;
+            let j = match index.entry(next.clone()) {
+                Entry::Vacant(entry) => {
+                    entry.insert(nodes.len());
+                    nodes.push((next.clone(), i));
+                    costs.push(next_cost);
+                    nodes.len() - 1
+                }
+                Entry::Occupied(entry) if next_cost < costs[*entry.get()] => {
+                    let j = *entry.get();
+                    nodes[j].1 = i;
+                    costs[j] = next_cost;
+                    j
+                }
+                Entry::Occupied(_) => continue,
+            };
+            queue.push((Reverse(next_cost + heuristic(&next)), next_cost, j));
+        }
+    }
+
+    None
+}
+
+// The steps between every pair of `sources` and everything they can reach. For squashing a big sparse graph,
+// like a cave of tunnels, down to the distances between the few nodes that matter.
+pub fn all_pairs<N, I>(
+    sources: impl IntoIterator<Item = N>,
+    mut neighbours: impl FnMut(&N) -> I,
+) -> HashMap<(N, N), usize>
+where
+    N: Clone + Eq + Hash,
+    I: IntoIterator<Item = N>,
+{
+    let mut distances = HashMap::new();
+    for source in sources {
+        for (node, steps) in bfs_distances(source.clone(), &mut neighbours) {
+            distances.insert((source.clone(), node), steps);
+        }
+    }
+    distances
+}
+
+// Floyd–Warshall: the cheapest cost between every pair of `n` nodes numbered from 0, given the edges as (from, to, cost).
+// None where there's no way through. Dense, so best for small graphs or when most pairs are wanted.
+pub fn floyd_warshall<C>(
+    n: usize,
+    edges: impl IntoIterator<Item = (usize, usize, C)>,
+) -> Vec<Vec<Option<C>>>
+where
+    C: Copy + Ord + Default + Add<Output = C>,
+{
+    let mut costs = vec![vec![None; n]; n];
+    for (i, row) in costs.iter_mut().enumerate() {
+        row[i] = Some(C::default());
+    }
+    for (from, to, cost) in edges {
+        if costs[from][to].is_none_or(|c| cost < c) {
+            costs[from][to] = Some(cost);
+        }
+    }
+
+    // Row k is read while row i is written, and they can be the same row, so this has to go by index.
+    #[allow(clippy::needless_range_loop)]
+    for k in 0..n {
+        for i in 0..n {
+            let Some(ik) = costs[i][k] else { continue };
+            for j in 0..n {
+                if let Some(kj) = costs[k][j] {
+                    if costs[i][j].is_none_or(|c| ik + kj < c) {
+                        costs[i][j] = Some(ik + kj);
+                    }
+                }
+            }
+        }
+    }
+
+    costs
+}
+
+#[cfg(test)]
+mod tests {
+    #[allow(unused_imports)]
+    use super::*;
+
+    // A little maze where '#' is a wall and the digits are how much it costs to step onto that square.
+    const MAZE: [&str; 3] = ["S91E", "1##1", "1111"];
+
+    fn find(c: char) -> (usize, usize) {
+        MAZE.iter()
+            .enumerate()
+            .find_map(|(y, row)| row.find(c).map(|x| (x, y)))
+            .unwrap()
+    }
+
+    fn open(&(x, y): &(usize, usize)) -> Vec<((usize, usize), u32)> {
+        let mut next = Vec::new();
+        for (dx, dy) in [(0, -1), (1, 0), (0, 1), (-1, 0)] {
+            let (Some(nx), Some(ny)) = (x.checked_add_signed(dx), y.checked_add_signed(dy)) else {
+                continue;
+            };
+            match MAZE.get(ny).and_then(|row| row.as_bytes().get(nx)) {
+                Some(b'#') | None => {}
+                Some(&c) => next.push(((nx, ny), (c as char).to_digit(10).unwrap_or(1))),
+            }
+        }
+        next
+    }
+
+    #[test]
+    fn searches_agree() {
+        let (start, end) = (find('S'), find('E'));
+
+        // The shortest way goes through a 9, but the cheapest goes around the long way.
+        let path = bfs(
+            start,
+            |p| open(p).into_iter().map(|(n, _)| n),
+            |p| *p == end,
+        )
+        .unwrap();
+        assert_eq!(path.cost, 3);
+        assert_eq!(path.nodes, vec![start, (1, 0), (2, 0), end]);
+
+        let cheapest = dijkstra(start, open, |p| *p == end).unwrap();
+        assert_eq!(cheapest.cost, 7);
+        assert!(!cheapest.nodes.contains(&(1, 0)));
+
+        let manhattan = |&(x, y): &(usize, usize)| (end.0.abs_diff(x) + end.1.abs_diff(y)) as u32;
+        assert_eq!(astar(start, open, manhattan, |p| *p == end), Some(cheapest));
+
+        let steps = bfs_distances(start, |p| open(p).into_iter().map(|(n, _)| n));
+        assert_eq!(steps[&end], 3);
+        assert_eq!(steps.len(), 10);
+        assert_eq!(bfs(start, |_| Vec::new(), |p| *p == end), None);
+    }
+
+    #[test]
+    fn all_pairs_agree() {
+        // A ring of five with a shortcut from 0 to 2, walked either way.
+        let edges = [(0, 1), (1, 2), (2, 3), (3, 4), (4, 0), (0, 2)];
+        let neighbours = |n: &usize| {
+            edges
+                .iter()
+                .filter_map(|&(a, b)| match *n {
+                    _ if a == *n => Some(b),
+                    _ if b == *n => Some(a),
+                    _ => None,
+                })
+                .collect::<Vec<_>>()
+        };
+        let steps = all_pairs(0..5, neighbours);
+        let costs = floyd_warshall(5, edges.iter().flat_map(|&(a, b)| [(a, b, 1), (b, a, 1)]));
+        for i in 0..5 {
+            for j in 0..5 {
+                assert_eq!(Some(steps[&(i, j)]), costs[i][j]);
+            }
+        }
+        assert_eq!(steps[&(1, 3)], 2);
+
+        // One way only, so there's no way back.
+        let costs = floyd_warshall(3, [(0, 1, 5), (1, 2, 2), (0, 2, 9)]);
+        assert_eq!(costs[0][2], Some(7));
+        assert_eq!(costs[2][0], None);
+    }
+}
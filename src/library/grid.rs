@@ -0,0 +1,338 @@
+// A dense 2D grid, for the puzzles that come as a picture of a map.
+// Positions are (x, y) with x going right along a row and y going down the rows, and (0, 0) is the top left.
+
+use super::InputError;
+use std::fmt;
+use std::ops::{Index, IndexMut};
+
+// Steps to the cells that share an edge, clockwise from up.
+pub const NEIGHBOURS4: [(isize, isize); 4] = [(0, -1), (1, 0), (0, 1), (-1, 0)];
+
+// Steps to every cell around, diagonals included, clockwise from up.
+pub const NEIGHBOURS8: [(isize, isize); 8] = [
+    (0, -1),
+    (1, -1),
+    (1, 0),
+    (1, 1),
+    (0, 1),
+    (-1, 1),
+    (-1, 0),
+    (-1, -1),
+];
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Grid<T> {
+    width: usize,
+    height: usize,
+    // Row by row, so the cell at (x, y) is at y * width + x.
+    cells: Vec<T>,
+}
+
+impl<T> Grid<T> {
+    // A grid with every cell set to `fill`.
+    pub fn new(width: usize, height: usize, fill: T) -> Self
+    where
+        T: Clone,
+    {
+        Grid {
+            width,
+            height,
+            cells: vec![fill; width * height],
+        }
+    }
+
+    // Build a grid from its rows, which all have to be the same length. Returns None if they aren't.
+    pub fn from_rows(rows: Vec<Vec<T>>) -> Option<Self> {
+        let width = rows.first().map_or(0, Vec::len);
+        if rows.iter().any(|row| row.len() != width) {
+            return None;
+        }
+        Some(Grid {
+            width,
+            height: rows.len(),
+            cells: rows.into_iter().flatten().collect(),
+        })
+    }
+
+    // Read a grid from the lines of the input, one character per cell. `cell` turns a character into a cell,
+    // or says what it expected instead, and the error points at the offending character.
+    pub fn parse<'a>(
+        data: impl IntoIterator<Item = &'a str>,
+        mut cell: impl FnMut(char) -> Result<T, String>,
+    ) -> Result<Self, InputError> {
+        // The first row says how wide the grid is, and every other row has to match it.
+        let mut lines = data.into_iter().peekable();
+        let width = lines.peek().map_or(0, |line| line.chars().count());
+        if width == 0 {
+            return Err(InputError::file("expected a grid"));
+        }
+
+        let mut cells = Vec::new();
+        let mut height = 0;
+        for (i, line) in lines.enumerate() {
+            let mut count = 0;
+            for (offset, c) in line.char_indices() {
+                cells.push(cell(c).map_err(|message| InputError::at(i, line, offset, message))?);
+                count += 1;
+            }
+            if count != width {
+                let offset = line
+                    .char_indices()
+                    .nth(width)
+                    .map_or(line.len(), |(o, _)| o);
+                return Err(InputError::at(
+                    i,
+                    line,
+                    offset,
+                    format!("expected a row of {width} cells, found {count}"),
+                ));
+            }
+            height += 1;
+        }
+
+        Ok(Grid {
+            width,
+            height,
+            cells,
+        })
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn get(&self, (x, y): (usize, usize)) -> Option<&T> {
+        (x < self.width && y < self.height).then(|| &self.cells[y * self.width + x])
+    }
+
+    pub fn get_mut(&mut self, (x, y): (usize, usize)) -> Option<&mut T> {
+        (x < self.width && y < self.height).then(|| &mut self.cells[y * self.width + x])
+    }
+
+    // Take a step from a position, as long as it stays on the grid.
+    pub fn step(&self, (x, y): (usize, usize), (dx, dy): (isize, isize)) -> Option<(usize, usize)> {
+        let x = x.checked_add_signed(dx).filter(|&x| x < self.width)?;
+        let y = y.checked_add_signed(dy).filter(|&y| y < self.height)?;
+        Some((x, y))
+    }
+
+    // The positions that share an edge with `pos` and are on the grid.
+    pub fn neighbours4(&self, pos: (usize, usize)) -> impl Iterator<Item = (usize, usize)> + '_ {
+        NEIGHBOURS4
+            .into_iter()
+            .filter_map(move |d| self.step(pos, d))
+    }
+
+    // The positions all around `pos`, diagonals included, that are on the grid.
+    pub fn neighbours8(&self, pos: (usize, usize)) -> impl Iterator<Item = (usize, usize)> + '_ {
+        NEIGHBOURS8
+            .into_iter()
+            .filter_map(move |d| self.step(pos, d))
+    }
+
+    // The positions seen looking from `pos` in a direction, nearest first, up to the edge. Doesn't include `pos` itself.
+    pub fn ray(
+        &self,
+        pos: (usize, usize),
+        dir: (isize, isize),
+    ) -> impl Iterator<Item = (usize, usize)> + '_ {
+        std::iter::successors(self.step(pos, dir), move |&p| self.step(p, dir))
+    }
+
+    pub fn row(&self, y: usize) -> &[T] {
+        &self.cells[y * self.width..(y + 1) * self.width]
+    }
+
+    pub fn rows(&self) -> impl DoubleEndedIterator<Item = &[T]> {
+        self.cells.chunks(self.width.max(1))
+    }
+
+    // A column past the right edge is empty, like `get` finding nothing there. That includes every column of an empty grid,
+    // which also keeps step_by from being given a width of 0.
+    pub fn column(&self, x: usize) -> impl DoubleEndedIterator<Item = &T> {
+        let cells = if x < self.width {
+            &self.cells[x..]
+        } else {
+            &[]
+        };
+        cells.iter().step_by(self.width.max(1))
+    }
+
+    // Every position, row by row.
+    pub fn positions(&self) -> impl Iterator<Item = (usize, usize)> {
+        let width = self.width;
+        (0..self.height).flat_map(move |y| (0..width).map(move |x| (x, y)))
+    }
+
+    // Every cell with its position, row by row.
+    pub fn iter(&self) -> impl Iterator<Item = ((usize, usize), &T)> {
+        self.positions().zip(self.cells.iter())
+    }
+
+    // The position of the first cell, row by row, that matches.
+    pub fn position(&self, f: impl FnMut(&T) -> bool) -> Option<(usize, usize)> {
+        let i = self.cells.iter().position(f)?;
+        Some((i % self.width, i / self.width))
+    }
+
+    pub fn map<U>(&self, f: impl FnMut(&T) -> U) -> Grid<U> {
+        Grid {
+            width: self.width,
+            height: self.height,
+            cells: self.cells.iter().map(f).collect(),
+        }
+    }
+
+    // Swap rows and columns, mirroring the grid along its main diagonal.
+    pub fn transpose(&self) -> Self
+    where
+        T: Clone,
+    {
+        self.remap(self.height, self.width, |x, y| (y, x))
+    }
+
+    // Turn the grid a quarter turn clockwise, so the left column becomes the top row.
+    pub fn rotate_right(&self) -> Self
+    where
+        T: Clone,
+    {
+        self.remap(self.height, self.width, |x, y| (y, self.height - 1 - x))
+    }
+
+    // Turn the grid a quarter turn anticlockwise, so the top row becomes the left column.
+    pub fn rotate_left(&self) -> Self
+    where
+        T: Clone,
+    {
+        self.remap(self.height, self.width, |x, y| (self.width - 1 - y, x))
+    }
+
+    // A new grid of the given size, where each cell (x, y) is copied from `from(x, y)` in this one.
+    fn remap(
+        &self,
+        width: usize,
+        height: usize,
+        from: impl Fn(usize, usize) -> (usize, usize),
+    ) -> Self
+    where
+        T: Clone,
+    {
+        let cells = (0..height)
+            .flat_map(|y| (0..width).map(move |x| (x, y)))
+            .map(|(x, y)| self[from(x, y)].clone())
+            .collect();
+        Grid {
+            width,
+            height,
+            cells,
+        }
+    }
+}
+
+impl<T> Index<(usize, usize)> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, pos: (usize, usize)) -> &T {
+        self.get(pos)
+            .unwrap_or_else(|| panic!("{pos:?} is outside a {}x{} grid", self.width, self.height))
+    }
+}
+
+impl<T> IndexMut<(usize, usize)> for Grid<T> {
+    fn index_mut(&mut self, pos: (usize, usize)) -> &mut T {
+        let (width, height) = (self.width, self.height);
+        self.get_mut(pos)
+            .unwrap_or_else(|| panic!("{pos:?} is outside a {width}x{height} grid"))
+    }
+}
+
+// Prints the cells of each row next to each other, with a line per row, which is how the puzzles draw them.
+impl<T: fmt::Display> fmt::Display for Grid<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for (y, row) in self.rows().enumerate() {
+            if y > 0 {
+                writeln!(f)?;
+            }
+            for cell in row {
+                write!(f, "{cell}")?;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #[allow(unused_imports)]
+    use super::*;
+
+    fn grid(text: &str) -> Grid<char> {
+        Grid::parse(text.lines(), Ok).unwrap()
+    }
+
+    #[test]
+    fn lookups() {
+        let g = grid("abc\ndef");
+        assert_eq!((g.width(), g.height()), (3, 2));
+        assert_eq!(g[(2, 1)], 'f');
+        assert_eq!(g.get((3, 0)), None);
+        assert_eq!(g.step((0, 0), (-1, 0)), None);
+        assert_eq!(
+            g.neighbours4((0, 0)).collect::<Vec<_>>(),
+            vec![(1, 0), (0, 1)]
+        );
+        assert_eq!(g.neighbours8((1, 0)).count(), 5);
+        assert_eq!(
+            g.ray((0, 0), (1, 0)).collect::<Vec<_>>(),
+            vec![(1, 0), (2, 0)]
+        );
+        assert_eq!(g.row(1), &['d', 'e', 'f']);
+        assert_eq!(g.column(1).rev().collect::<String>(), "eb");
+        assert_eq!(g.column(3).count(), 0);
+        assert_eq!(g.position(|&c| c == 'e'), Some((1, 1)));
+        assert_eq!(g.iter().nth(4), Some(((1, 1), &'e')));
+
+        let empty = Grid::<char>::new(0, 0, '.');
+        assert_eq!(empty.column(0).count(), 0);
+        assert_eq!(empty.rows().count(), 0);
+    }
+
+    #[test]
+    fn turns() {
+        let g = grid("abc\ndef");
+        assert_eq!(g.to_string(), "abc\ndef");
+        assert_eq!(g.transpose().to_string(), "ad\nbe\ncf");
+        assert_eq!(g.rotate_right().to_string(), "da\neb\nfc");
+        assert_eq!(g.rotate_left().to_string(), "cf\nbe\nad");
+        assert_eq!(g.rotate_right().rotate_left(), g);
+        assert_eq!(
+            Grid::from_rows(vec![vec![1, 2], vec![3, 4]])
+                .unwrap()
+                .map(|n| n * 2)[(1, 1)],
+            8
+        );
+        assert!(Grid::from_rows(vec![vec![1, 2], vec![3]]).is_none());
+    }
+
+    #[test]
+    fn parse_errors() {
+        let lines = ["301", "2x5", "65"];
+        let digit = |c: char| {
+            c.to_digit(10)
+                .ok_or(format!("expected a digit, found '{c}'"))
+        };
+        let e = Grid::parse(lines, digit).unwrap_err();
+        assert_eq!((e.line, e.column), (2, 2));
+
+        let lines = ["301", "65"];
+        let e = Grid::parse(lines, digit).unwrap_err();
+        assert_eq!(
+            (e.line, e.column, e.message.as_str()),
+            (2, 3, "expected a row of 3 cells, found 2")
+        );
+    }
+}
@@ -0,0 +1,202 @@
+// Sets of whole numbers kept as sorted, inclusive ranges, for puzzles about stretches of a line rather than single points.
+// A range like (2, 4) covers 2, 3 and 4. Ranges that overlap or touch are merged, so the set never holds two that could be one.
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct IntervalSet {
+    // Sorted by start, with a gap of at least one number between each range and the next.
+    ranges: Vec<(i64, i64)>,
+}
+
+impl IntervalSet {
+    pub fn new() -> Self {
+        IntervalSet::default()
+    }
+
+    // Add a range, merging it with any ranges it overlaps or touches. A range that ends before it starts is empty.
+    pub fn insert(&mut self, (start, end): (i64, i64)) {
+        if start > end {
+            return;
+        }
+
+        // The ranges from i up to j are the ones that overlap or touch the new one, and get replaced by it.
+        let i = self
+            .ranges
+            .partition_point(|r| r.1.saturating_add(1) < start);
+        let j = self
+            .ranges
+            .partition_point(|r| r.0 <= end.saturating_add(1));
+        let merged = match self.ranges[i..j] {
+            [] => (start, end),
+            [only] => (start.min(only.0), end.max(only.1)),
+            [first, .., last] => (start.min(first.0), end.max(last.1)),
+        };
+        self.ranges.splice(i..j, [merged]);
+    }
+
+    // How many numbers are in the set.
+    pub fn len(&self) -> i64 {
+        self.ranges.iter().map(|r| r.1 - r.0 + 1).sum()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.ranges.is_empty()
+    }
+
+    // The merged ranges, in order.
+    pub fn iter(&self) -> impl Iterator<Item = (i64, i64)> + '_ {
+        self.ranges.iter().copied()
+    }
+
+    pub fn contains(&self, n: i64) -> bool {
+        self.contains_range((n, n))
+    }
+
+    // Whether every number in the range is in the set.
+    pub fn contains_range(&self, (start, end): (i64, i64)) -> bool {
+        let i = self.ranges.partition_point(|r| r.1 < start);
+        self.ranges
+            .get(i)
+            .is_some_and(|r| r.0 <= start && end <= r.1)
+    }
+
+    // Whether any number in the range is in the set.
+    pub fn overlaps(&self, (start, end): (i64, i64)) -> bool {
+        let i = self.ranges.partition_point(|r| r.1 < start);
+        start <= end && self.ranges.get(i).is_some_and(|r| r.0 <= end)
+    }
+
+    // The numbers in either set.
+    pub fn union(&self, other: &IntervalSet) -> IntervalSet {
+        self.iter().chain(other.iter()).collect()
+    }
+
+    // The numbers in both sets. Walks along both lists of ranges at once, moving on from whichever ends first.
+    pub fn intersection(&self, other: &IntervalSet) -> IntervalSet {
+        let mut ranges = Vec::new();
+        let (mut i, mut j) = (0, 0);
+
+        while let (Some(a), Some(b)) = (self.ranges.get(i), other.ranges.get(j)) {
+            let (start, end) = (a.0.max(b.0), a.1.min(b.1));
+            if start <= end {
+                ranges.push((start, end));
+            }
+            if a.1 < b.1 {
+                i += 1;
+            } else {
+                j += 1;
+            }
+        }
+
+        IntervalSet { ranges }
+    }
+
+    // The numbers between `start` and `end` that aren't in the set, such as the one spot a row of sensors can't see.
+    pub fn gaps(&self, (start, end): (i64, i64)) -> IntervalSet {
+        let mut ranges = Vec::new();
+        let mut next = start;
+
+        for &(a, b) in &self.ranges {
+            if b < next {
+                continue;
+            }
+            if a > end {
+                break;
+            }
+            if a > next {
+                ranges.push((next, a - 1));
+            }
+            next = b.saturating_add(1);
+        }
+        if next <= end {
+            ranges.push((next, end));
+        }
+
+        IntervalSet { ranges }
+    }
+}
+
+impl From<(i64, i64)> for IntervalSet {
+    fn from(range: (i64, i64)) -> Self {
+        let mut set = IntervalSet::new();
+        set.insert(range);
+        set
+    }
+}
+
+// Collecting sorts the ranges once and merges them in a single pass, rather than inserting them one by one.
+impl FromIterator<(i64, i64)> for IntervalSet {
+    fn from_iter<I: IntoIterator<Item = (i64, i64)>>(iter: I) -> Self {
+        let mut sorted = iter.into_iter().filter(|r| r.0 <= r.1).collect::<Vec<_>>();
+        sorted.sort_unstable();
+
+        let mut ranges: Vec<(i64, i64)> = Vec::with_capacity(sorted.len());
+        for (start, end) in sorted {
+            match ranges.last_mut() {
+                Some(last) if start <= last.1.saturating_add(1) => last.1 = last.1.max(end),
+                _ => ranges.push((start, end)),
+            }
+        }
+
+        IntervalSet { ranges }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #[allow(unused_imports)]
+    use super::*;
+
+    #[test]
+    fn merging() {
+        let mut set = IntervalSet::new();
+        set.insert((10, 12));
+        set.insert((1, 3));
+        set.insert((5, 6));
+        assert_eq!(
+            set.iter().collect::<Vec<_>>(),
+            vec![(1, 3), (5, 6), (10, 12)]
+        );
+        assert_eq!(set.len(), 8);
+
+        // Touching counts, so 4 joins the first two ranges together.
+        set.insert((4, 4));
+        assert_eq!(set.iter().collect::<Vec<_>>(), vec![(1, 6), (10, 12)]);
+        set.insert((0, 20));
+        assert_eq!(set, IntervalSet::from((0, 20)));
+        set.insert((5, 2));
+        assert_eq!(set.len(), 21);
+
+        let collected = [(10, 12), (5, 6), (4, 4), (1, 3), (2, 2)]
+            .into_iter()
+            .collect::<IntervalSet>();
+        assert_eq!(collected.iter().collect::<Vec<_>>(), vec![(1, 6), (10, 12)]);
+        assert!(IntervalSet::new().is_empty());
+    }
+
+    #[test]
+    fn queries() {
+        let a = [(1, 6), (10, 12)].into_iter().collect::<IntervalSet>();
+        let b = [(5, 11), (20, 21)].into_iter().collect::<IntervalSet>();
+
+        assert!(a.contains(6) && !a.contains(7) && !a.contains(0));
+        assert!(a.contains_range((2, 5)));
+        assert!(!a.contains_range((5, 10)));
+        assert!(a.overlaps((6, 9)));
+        assert!(!a.overlaps((7, 9)));
+
+        assert_eq!(
+            a.intersection(&b).iter().collect::<Vec<_>>(),
+            vec![(5, 6), (10, 11)]
+        );
+        assert_eq!(
+            a.union(&b).iter().collect::<Vec<_>>(),
+            vec![(1, 12), (20, 21)]
+        );
+        assert_eq!(
+            a.gaps((0, 15)).iter().collect::<Vec<_>>(),
+            vec![(0, 0), (7, 9), (13, 15)]
+        );
+        assert!(a.gaps((2, 5)).is_empty());
+        assert_eq!(a.gaps((0, 15)).len() + a.len(), 16);
+    }
+}
@@ -0,0 +1,520 @@
+// Module to handle/act as a library for all my various solutions.
+
+// Imports
+use serde::{Deserialize, Serialize};
+use std::any::Any;
+use std::fmt;
+use std::fs;
+use std::io::{self, Read};
+
+// Reusable nom parsers for the common bits of puzzle input.
+pub mod parse;
+
+// A dense 2D grid for the map puzzles. Not every day needs all of it, so unused parts aren't warned about.
+#[allow(dead_code)]
+pub mod grid;
+pub use grid::Grid;
+
+// Points, directions and the arithmetic on them.
+#[allow(dead_code)]
+pub mod point;
+pub use point::{Direction, Point2, Point3};
+
+// Cycle detection, for simulations that run for far too many steps to do one at a time.
+#[allow(dead_code)]
+pub mod cycle;
+
+// Shortest path searches, for mazes, networks of tunnels and anything else that can be walked one step at a time.
+#[allow(dead_code)]
+pub mod graph;
+
+// Sets of numbers stored as ranges, for counting what a bunch of overlapping ranges cover.
+#[allow(dead_code)]
+pub mod interval;
+pub use interval::IntervalSet;
+
+// Point sets backed by a bitset, for simulations that spend their time asking whether a cell is taken.
+#[allow(dead_code)]
+pub mod bitset;
+pub use bitset::PointSet;
+
+// Dense 3D grids of voxels, with flood fills and surface areas.
+#[allow(dead_code)]
+pub mod voxel;
+pub use voxel::VoxelGrid;
+
+// Checked and arbitrary precision integers, for the days whose numbers can overflow.
+#[allow(dead_code)]
+pub mod num;
+
+// Read a file from a given path and return a vector of strings. A path of "-" reads from stdin instead.
+// Panics if the file can't be read, see `load` for the fallible version.
+#[allow(unused)]
+pub fn read_file(path: &str) -> Vec<String> {
+    load(path).unwrap_or_else(|e| panic!("{e}"))
+}
+
+// Read a file into lines like `read_file`, but return an error saying which file went wrong instead of panicking.
+pub fn load(path: &str) -> Result<Vec<String>, InputError> {
+    RawInput::load(path).map(|input| input.to_lines())
+}
+
+// A whole input file, read once. Days can borrow it as text, bytes, lines or blocks, instead of getting a String per line.
+pub struct RawInput {
+    text: String,
+}
+
+impl RawInput {
+    // Read a whole file, or stdin for a path of "-".
+    pub fn load(path: &str) -> Result<Self, InputError> {
+        let text = if path == "-" {
+            let mut text = String::new();
+            io::stdin().read_to_string(&mut text).map(|_| text)
+        } else {
+            fs::read_to_string(path)
+        };
+        text.map(RawInput::new)
+            .map_err(|e| InputError::file(format!("could not read file: {e}")).in_file(path))
+    }
+
+    // Inputs are normalized as they come in: a byte order mark is dropped and CRLF line endings become LF, which no day cares about.
+    pub fn new(text: impl Into<String>) -> Self {
+        let mut text = text.into();
+        if let Some(stripped) = text.strip_prefix('\u{feff}') {
+            text = stripped.to_string();
+        }
+        if text.contains('\r') {
+            text = text.replace("\r\n", "\n");
+        }
+        RawInput { text }
+    }
+
+    // The input with trailing whitespace removed from every line, or None if there wasn't any so the usual case doesn't copy anything.
+    pub fn trim_lines(&self) -> Option<RawInput> {
+        if self.lines().all(|line| line.len() == line.trim_end().len()) {
+            return None;
+        }
+        let mut text = self
+            .lines()
+            .map(str::trim_end)
+            .collect::<Vec<_>>()
+            .join("\n");
+        if self.text.ends_with('\n') {
+            text.push('\n');
+        }
+        Some(RawInput { text })
+    }
+
+    pub fn as_str(&self) -> &str {
+        &self.text
+    }
+
+    pub fn as_bytes(&self) -> &[u8] {
+        self.text.as_bytes()
+    }
+
+    pub fn lines(&self) -> std::str::Lines<'_> {
+        self.text.lines()
+    }
+
+    // Groups of lines separated by blank lines, each without its trailing newline, and with the index of its first line so errors can say where they are.
+    // Runs of blank lines, and blank lines at either end, don't make empty groups, so a missing or extra trailing newline doesn't matter.
+    // A line of nothing but spaces counts as blank too, since days that keep their whitespace can still have one between blocks.
+    pub fn blocks(&self) -> Vec<(usize, &str)> {
+        let mut blocks = Vec::new();
+        // The index of the block's first line, its byte offset, and where its last line ends.
+        let mut start = None;
+        let mut end = 0;
+        let mut offset = 0;
+
+        for (i, line) in self.text.split_inclusive('\n').enumerate() {
+            let content = line.trim_end_matches(['\n', '\r']);
+            if content.trim().is_empty() {
+                if let Some((index, start)) = start.take() {
+                    blocks.push((index, &self.text[start..end]));
+                }
+            } else {
+                start.get_or_insert((i, offset));
+                end = offset + content.len();
+            }
+            offset += line.len();
+        }
+        if let Some((index, start)) = start {
+            blocks.push((index, &self.text[start..end]));
+        }
+
+        blocks
+    }
+
+    // Copy the lines out, for parsers that work on `&[String]`.
+    pub fn to_lines(&self) -> Vec<String> {
+        self.lines().map(String::from).collect()
+    }
+}
+
+// Path to the puzzle input for a given day, relative to the base directory of the project.
+pub fn input_path(day: u32) -> String {
+    input_path_in("data", day)
+}
+
+// The same for inputs kept in another directory, like the examples in testdata.
+pub fn input_path_in(dir: &str, day: u32) -> String {
+    format!("{dir}/day{day:02}.txt")
+}
+
+// Where and why an input couldn't be read or parsed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct InputError {
+    // Empty until whoever read the file fills it in, since parsers only see the lines.
+    pub path: String,
+    // 1-based line and column, or 0 when the error isn't about a particular line.
+    pub line: usize,
+    pub column: usize,
+    // The offending line.
+    pub text: String,
+    pub message: String,
+}
+
+impl InputError {
+    // An error about a whole file rather than a line in it.
+    pub fn file(message: impl Into<String>) -> Self {
+        InputError {
+            path: String::new(),
+            line: 0,
+            column: 0,
+            text: String::new(),
+            message: message.into(),
+        }
+    }
+
+    // An error on a line of the input. `index` counts lines from 0, like the index into the data, and `offset` is a byte offset into the line.
+    pub fn at(index: usize, text: &str, offset: usize, message: impl Into<String>) -> Self {
+        InputError {
+            path: String::new(),
+            line: index + 1,
+            column: text.get(..offset).map_or(1, |s| s.chars().count() + 1),
+            text: text.to_string(),
+            message: message.into(),
+        }
+    }
+
+    // An error about part of a line, pointing at where that part starts. `part` should be a slice of `text`.
+    pub fn on(index: usize, text: &str, part: &str, message: impl Into<String>) -> Self {
+        let offset = (part.as_ptr() as usize)
+            .checked_sub(text.as_ptr() as usize)
+            .filter(|&o| o <= text.len())
+            .or_else(|| text.find(part))
+            .unwrap_or(0);
+        Self::at(index, text, offset, message)
+    }
+
+    pub fn in_file(mut self, path: &str) -> Self {
+        self.path = path.to_string();
+        self
+    }
+
+    // The offending line with a caret under the column, to print under the error.
+    pub fn snippet(&self) -> String {
+        format!(
+            "    {}\n    {}^",
+            self.text,
+            " ".repeat(self.column.saturating_sub(1))
+        )
+    }
+}
+
+impl fmt::Display for InputError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let path = if self.path.is_empty() {
+            "input"
+        } else {
+            &self.path
+        };
+        match self.line {
+            0 => write!(f, "{path}: {}", self.message),
+            line => write!(f, "{path}:{line}:{}: {}", self.column, self.message),
+        }
+    }
+}
+
+impl std::error::Error for InputError {}
+
+// Parse a part of a line, e.g. a number, reporting where it is if it doesn't parse.
+pub fn parse_field<T>(index: usize, text: &str, part: &str) -> Result<T, InputError>
+where
+    T: std::str::FromStr,
+    T::Err: fmt::Display,
+{
+    part.parse()
+        .map_err(|e| InputError::on(index, text, part, format!("invalid value '{part}': {e}")))
+}
+
+// Every integer in a line, in order, including negative ones. A '-' between two numbers, like in 2-4, is a separator rather than a sign.
+pub fn ints<T>(index: usize, text: &str) -> Result<Vec<T>, InputError>
+where
+    T: std::str::FromStr,
+    T::Err: fmt::Display,
+{
+    let bytes = text.as_bytes();
+    let mut numbers = Vec::new();
+    let mut i = 0;
+
+    while i < bytes.len() {
+        let signed = bytes[i] == b'-' && (i == 0 || !bytes[i - 1].is_ascii_digit());
+        let digits = i + signed as usize;
+        let mut end = digits;
+        while end < bytes.len() && bytes[end].is_ascii_digit() {
+            end += 1;
+        }
+
+        if end > digits {
+            numbers.push(parse_field(index, text, &text[i..end])?);
+            i = end;
+        } else {
+            i += 1;
+        }
+    }
+
+    Ok(numbers)
+}
+
+// A single day's puzzle. Parse the input once, then solve each part from the parsed input.
+pub trait Solution {
+    const DAY: u32;
+    const TITLE: &'static str;
+
+    // Whether trailing whitespace is trimmed from every line before parsing. Puzzles where it's part of the picture turn this off.
+    const TRIM_WHITESPACE: bool = true;
+
+    // What the input is parsed into, and the answer type for each part.
+    type Input: 'static;
+    type Part1: Into<Answer>;
+    type Part2: Into<Answer>;
+
+    fn parse(input: &RawInput) -> Result<Self::Input, InputError>;
+    fn part1(input: &Self::Input) -> Self::Part1;
+    fn part2(input: &Self::Input) -> Self::Part2;
+
+    // Solve both parts at once. Days that get both answers out of a single pass override this.
+    fn solve(input: &Self::Input) -> (Self::Part1, Self::Part2) {
+        (Self::part1(input), Self::part2(input))
+    }
+}
+
+// A single part's answer. Days answer with different types, so they all get converted into this to be printed, compared and stored.
+#[derive(Debug, Clone, Eq, Serialize, Deserialize)]
+#[serde(tag = "kind", content = "value", rename_all = "lowercase")]
+pub enum Answer {
+    Int(i64),
+    BigInt(i128),
+    Text(String),
+    // Multi-line answers, like the CRT screen on day 10.
+    Grid(Vec<String>),
+}
+
+impl Answer {
+    // Integer value of the answer, regardless of which integer variant holds it.
+    pub fn as_integer(&self) -> Option<i128> {
+        match self {
+            Answer::Int(n) => Some(*n as i128),
+            Answer::BigInt(n) => Some(*n),
+            _ => None,
+        }
+    }
+
+    pub fn is_multiline(&self) -> bool {
+        matches!(self, Answer::Grid(_))
+    }
+}
+
+// Integers compare by value, so an Int and a BigInt holding the same number are equal.
+impl PartialEq for Answer {
+    fn eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            (Answer::Text(a), Answer::Text(b)) => a == b,
+            (Answer::Grid(a), Answer::Grid(b)) => a == b,
+            _ => self.as_integer().is_some() && self.as_integer() == other.as_integer(),
+        }
+    }
+}
+
+impl fmt::Display for Answer {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Answer::Int(n) => write!(f, "{n}"),
+            Answer::BigInt(n) => write!(f, "{n}"),
+            Answer::Text(s) => write!(f, "{s}"),
+            Answer::Grid(rows) => write!(f, "{}", rows.join("\n")),
+        }
+    }
+}
+
+impl From<i32> for Answer {
+    fn from(n: i32) -> Self {
+        Answer::Int(n as i64)
+    }
+}
+
+impl From<i64> for Answer {
+    fn from(n: i64) -> Self {
+        Answer::Int(n)
+    }
+}
+
+// Only use the big variant when the number doesn't fit in an i64.
+impl From<i128> for Answer {
+    fn from(n: i128) -> Self {
+        i64::try_from(n).map_or(Answer::BigInt(n), Answer::Int)
+    }
+}
+
+impl From<String> for Answer {
+    fn from(s: String) -> Self {
+        Answer::Text(s)
+    }
+}
+
+impl From<&str> for Answer {
+    fn from(s: &str) -> Self {
+        Answer::Text(s.to_string())
+    }
+}
+
+impl From<Vec<String>> for Answer {
+    fn from(rows: Vec<String>) -> Self {
+        Answer::Grid(rows)
+    }
+}
+
+// Which parts of a puzzle to solve.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Part {
+    One,
+    Two,
+    Both,
+}
+
+impl Part {
+    // The part numbers this selects.
+    pub fn numbers(self) -> &'static [u8] {
+        match self {
+            Part::One => &[1],
+            Part::Two => &[2],
+            Part::Both => &[1, 2],
+        }
+    }
+}
+
+// Type-erased view of a Solution, so days with different input and answer types can share one registry.
+pub trait Day: Sync {
+    fn number(&self) -> u32;
+    fn title(&self) -> &'static str;
+    fn parse_input(&self, input: &RawInput) -> Result<Box<dyn Any>, InputError>;
+    fn solve_input(&self, input: &dyn Any, part: Part) -> (Option<Answer>, Option<Answer>);
+}
+
+impl<S: Solution + Sync> Day for S {
+    fn number(&self) -> u32 {
+        S::DAY
+    }
+
+    fn title(&self) -> &'static str {
+        S::TITLE
+    }
+
+    fn parse_input(&self, input: &RawInput) -> Result<Box<dyn Any>, InputError> {
+        let trimmed = S::TRIM_WHITESPACE.then(|| input.trim_lines()).flatten();
+        Ok(Box::new(S::parse(trimmed.as_ref().unwrap_or(input))?))
+    }
+
+    fn solve_input(&self, input: &dyn Any, part: Part) -> (Option<Answer>, Option<Answer>) {
+        let input = input
+            .downcast_ref::<S::Input>()
+            .expect("Input was parsed by a different day");
+        match part {
+            Part::One => (Some(S::part1(input).into()), None),
+            Part::Two => (None, Some(S::part2(input).into())),
+            Part::Both => {
+                let (p1, p2) = S::solve(input);
+                (Some(p1.into()), Some(p2.into()))
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #[allow(unused_imports)]
+    use super::*;
+
+    #[test]
+    fn answer_integers_compare_by_value() {
+        assert_eq!(Answer::from(152i128), Answer::Int(152));
+        assert_eq!(Answer::BigInt(301), Answer::Int(301));
+        assert_ne!(Answer::Int(5), Answer::from("5"));
+        assert_eq!(Answer::from(i128::MAX), Answer::BigInt(i128::MAX));
+    }
+
+    #[test]
+    fn input_errors_point_at_the_problem() {
+        let line = "move 1 from x to 1";
+        let to = line.split(' ').nth(3).unwrap();
+        let error = parse_field::<i32>(4, line, to)
+            .unwrap_err()
+            .in_file("data/day05.txt");
+        assert_eq!((error.line, error.column), (5, 13));
+        assert_eq!(
+            error.to_string(),
+            "data/day05.txt:5:13: invalid value 'x': invalid digit found in string"
+        );
+        assert_eq!(error.snippet(), "    move 1 from x to 1\n                ^");
+
+        assert!(load("testdata/nothing.txt").is_err());
+    }
+
+    #[test]
+    fn raw_input_views() {
+        let input = RawInput::new("1000\n2000\n\n\n3000\n\n4000\n5000");
+        assert_eq!(input.lines().count(), 8);
+        assert_eq!(input.lines().next(), Some("1000"));
+        assert_eq!(
+            input.blocks(),
+            vec![(0, "1000\n2000"), (4, "3000"), (6, "4000\n5000")]
+        );
+        assert_eq!(input.as_bytes()[0], b'1');
+        assert!(RawInput::new("").blocks().is_empty());
+        assert_eq!(
+            RawInput::new("\n    [D]\n 1 \n  \n\nmove 1\n ").blocks(),
+            vec![(1, "    [D]\n 1 "), (5, "move 1")]
+        );
+    }
+
+    #[test]
+    fn normalize_input() {
+        let input = RawInput::new("\u{feff}A Y\r\nB X \r\n\r\n");
+        assert_eq!(input.as_bytes(), b"A Y\nB X \n\n");
+        assert_eq!(input.trim_lines().unwrap().as_bytes(), b"A Y\nB X\n\n");
+        assert!(RawInput::new("A Y\n    [D]\n").trim_lines().is_none());
+    }
+
+    #[test]
+    fn ints_in_a_line() {
+        let line = "Sensor at x=-2, y=15: closest beacon is at x=10, y=-16";
+        assert_eq!(ints::<i32>(0, line).unwrap(), vec![-2, 15, 10, -16]);
+        assert_eq!(ints::<i32>(0, "2-4,6-8").unwrap(), vec![2, 4, 6, 8]);
+        assert!(ints::<i32>(0, "no numbers - here").unwrap().is_empty());
+        assert_eq!(ints::<u8>(3, "x=300").unwrap_err().column, 3);
+    }
+
+    #[test]
+    fn answer_serde_round_trip() {
+        let answers = vec![
+            Answer::from(24000),
+            Answer::from(i128::MAX),
+            Answer::from("CMZ"),
+            Answer::from(vec!["##..".to_string(), "..##".to_string()]),
+        ];
+        let json = serde_json::to_string(&answers).unwrap();
+        assert_eq!(serde_json::from_str::<Vec<Answer>>(&json).unwrap(), answers);
+    }
+}
@@ -0,0 +1,155 @@
+// Numbers for the days whose sums can get out of hand, like monkeys squaring their worries or a tree of monkeys shouting results up to the root.
+// Those days are written against the Number trait, so the same code can run on plain integers, on Checked integers that
+// panic naming the exact operation that overflowed, or with the `bigint` feature, on integers that never overflow at all.
+
+use super::Answer;
+use std::fmt;
+use std::ops::{Add, Div, Mul, Rem, Sub};
+
+pub trait Number:
+    Clone
+    + Ord
+    + fmt::Debug
+    + fmt::Display
+    + From<i64>
+    + Add<Output = Self>
+    + Sub<Output = Self>
+    + Mul<Output = Self>
+    + Div<Output = Self>
+    + Rem<Output = Self>
+{
+    // The value as an i64, if it fits. For turning a small result back into something like an index or a digit.
+    fn to_i64(&self) -> Option<i64>;
+}
+
+impl Number for i64 {
+    fn to_i64(&self) -> Option<i64> {
+        Some(*self)
+    }
+}
+
+impl Number for i128 {
+    fn to_i64(&self) -> Option<i64> {
+        i64::try_from(*self).ok()
+    }
+}
+
+// An integer where every operation is checked, and one that doesn't fit panics with the operation instead of wrapping around.
+// The runner catches the panic, so a bad input shows up as something like "panicked: 4611686018427387904 * 2 overflows an i64".
+// Not Copy, like a big integer, so code that works on one works on the other.
+#[derive(Debug, Clone, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Checked<T>(pub T);
+
+// The number types the days use, picked by the `bigint` feature. Int is for sums that fit in an i64 and WideInt for those that need an i128.
+#[cfg(not(feature = "bigint"))]
+pub type Int = Checked<i64>;
+#[cfg(not(feature = "bigint"))]
+pub type WideInt = Checked<i128>;
+#[cfg(feature = "bigint")]
+pub type Int = num_bigint::BigInt;
+#[cfg(feature = "bigint")]
+pub type WideInt = num_bigint::BigInt;
+
+impl<T: fmt::Display> fmt::Display for Checked<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+// Every operation is the same apart from the method and the symbol, so write them out for each width at once.
+macro_rules! checked {
+    ($t:ty, $name:literal) => {
+        checked!(@op $t, $name, Add, add, checked_add, "+");
+        checked!(@op $t, $name, Sub, sub, checked_sub, "-");
+        checked!(@op $t, $name, Mul, mul, checked_mul, "*");
+        checked!(@op $t, $name, Div, div, checked_div, "/");
+        checked!(@op $t, $name, Rem, rem, checked_rem, "%");
+
+        impl From<i64> for Checked<$t> {
+            fn from(n: i64) -> Self {
+                Checked(n.into())
+            }
+        }
+
+        impl Number for Checked<$t> {
+            fn to_i64(&self) -> Option<i64> {
+                i64::try_from(self.0).ok()
+            }
+        }
+
+        impl From<Checked<$t>> for Answer {
+            fn from(n: Checked<$t>) -> Self {
+                n.0.into()
+            }
+        }
+    };
+
+    (@op $t:ty, $name:literal, $trait:ident, $method:ident, $checked:ident, $symbol:literal) => {
+        impl $trait for Checked<$t> {
+            type Output = Self;
+
+            fn $method(self, other: Self) -> Self {
+                match self.0.$checked(other.0) {
+                    Some(n) => Checked(n),
+                    None if other.0 == 0 && matches!($symbol, "/" | "%") => {
+                        panic!("{} {} 0 divides by zero", self, $symbol)
+                    }
+                    None => panic!("{} {} {} overflows {}", self, $symbol, other, $name),
+                }
+            }
+        }
+    };
+}
+
+checked!(i64, "an i64");
+checked!(i128, "an i128");
+
+#[cfg(feature = "bigint")]
+impl Number for num_bigint::BigInt {
+    fn to_i64(&self) -> Option<i64> {
+        i64::try_from(self).ok()
+    }
+}
+
+// Big answers that don't even fit in an i128 can still be printed and compared as text.
+#[cfg(feature = "bigint")]
+impl From<num_bigint::BigInt> for Answer {
+    fn from(n: num_bigint::BigInt) -> Self {
+        i128::try_from(&n).map_or_else(|_| Answer::Text(n.to_string()), Answer::from)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #[allow(unused_imports)]
+    use super::*;
+
+    // Something generic to run in each mode.
+    fn factorial<N: Number>(n: i64) -> N {
+        (1..=n).fold(N::from(1), |acc, i| acc * N::from(i))
+    }
+
+    #[test]
+    fn modes_agree() {
+        assert_eq!(factorial::<i64>(20), 2432902008176640000);
+        assert_eq!(factorial::<Checked<i64>>(20), Checked(2432902008176640000));
+        assert_eq!(factorial::<Checked<i128>>(25).to_i64(), None);
+        assert_eq!(
+            factorial::<Checked<i128>>(25).to_string(),
+            "15511210043330985984000000"
+        );
+        assert_eq!(Checked(7i64) % Checked(4), Checked(3));
+        assert_eq!(Answer::from(Checked(5i128)), Answer::Int(5));
+    }
+
+    #[test]
+    fn overflow_names_the_operation() {
+        let result = std::panic::catch_unwind(|| factorial::<Checked<i64>>(21));
+        let message = *result.unwrap_err().downcast::<String>().unwrap();
+        assert_eq!(message, "2432902008176640000 * 21 overflows an i64");
+
+        let result = std::panic::catch_unwind(|| Checked(1i64) / Checked(0));
+        let message = *result.unwrap_err().downcast::<String>().unwrap();
+        assert_eq!(message, "1 / 0 divides by zero");
+    }
+}
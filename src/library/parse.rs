@@ -0,0 +1,210 @@
+// Small nom parsers for the shapes that keep turning up in puzzle inputs: numbers, coordinates, ranges, paths and "key=value" sentences.
+// Days build their line parsers out of these, then run them with `line` or `lines` to get an InputError pointing at whatever didn't match.
+
+use super::InputError;
+use nom::branch::alt;
+use nom::bytes::complete::{tag, take_until};
+use nom::character::complete::{alphanumeric1, char, digit1, space0};
+use nom::combinator::{eof, opt, recognize};
+use nom::error::{context, ErrorKind, ParseError, VerboseError, VerboseErrorKind};
+use nom::multi::separated_list1;
+use nom::sequence::{pair, preceded, separated_pair, terminated, tuple};
+use nom::IResult;
+use std::str::FromStr;
+
+// Every parser here keeps the trail of contexts it went through, so errors can say what was expected.
+pub type Res<'a, T> = IResult<&'a str, T, VerboseError<&'a str>>;
+
+// Run a parser over a whole line, which is at `index` in the input. Trailing whitespace is fine, anything else left over is an error.
+pub fn line<'a, T>(
+    index: usize,
+    text: &'a str,
+    parser: impl FnMut(&'a str) -> Res<'a, T>,
+) -> Result<T, InputError> {
+    let mut whole = terminated(parser, pair(space0, context("the end of the line", eof)));
+    match whole(text) {
+        Ok((_, value)) => Ok(value),
+        Err(nom::Err::Error(e) | nom::Err::Failure(e)) => Err(error(index, text, e)),
+        // Everything is parsed from complete lines, so there's never more input to wait for.
+        Err(nom::Err::Incomplete(_)) => unreachable!("complete parsers never ask for more input"),
+    }
+}
+
+// Run a parser over every line, such as those borrowed from a RawInput, stopping at the first one that doesn't parse.
+pub fn lines<'a, T>(
+    data: impl IntoIterator<Item = &'a str>,
+    mut parser: impl FnMut(&'a str) -> Res<'a, T>,
+) -> Result<Vec<T>, InputError> {
+    data.into_iter()
+        .enumerate()
+        .map(|(i, text)| line(i, text, &mut parser))
+        .collect()
+}
+
+// Turn nom's trail of errors into one InputError. The innermost error is where parsing stopped,
+// and the innermost context is the most specific thing it was looking for there.
+fn error(index: usize, text: &str, e: VerboseError<&str>) -> InputError {
+    let offset = match e.errors.first() {
+        // `after` and `field` looked for their prefix all the way to the end without finding it.
+        Some((_, VerboseErrorKind::Nom(ErrorKind::TakeUntil))) | None => text.len(),
+        Some((rest, _)) => text.len() - rest.len(),
+    };
+    let expected = e.errors.iter().find_map(|(_, kind)| match kind {
+        VerboseErrorKind::Context(what) => Some(format!("expected {what}")),
+        VerboseErrorKind::Char(c) => Some(format!("expected '{c}'")),
+        VerboseErrorKind::Nom(_) => None,
+    });
+    let message = expected.unwrap_or_else(|| match text[offset..].split_whitespace().next() {
+        Some(word) => format!("unexpected '{word}'"),
+        None => "unexpected end of line".to_string(),
+    });
+    InputError::at(index, text, offset, message)
+}
+
+// A whole number, with an optional minus sign. It has to fit in T, so a sign on an unsigned type is an error too.
+pub fn int<'a, T: FromStr>(input: &'a str) -> Res<'a, T> {
+    let (rest, digits) = context("a number", recognize(pair(opt(char('-')), digit1)))(input)?;
+    match digits.parse() {
+        Ok(n) => Ok((rest, n)),
+        // It is a number, just not one that fits, so don't let an alt try something else instead.
+        Err(_) => Err(nom::Err::Failure(VerboseError {
+            errors: vec![
+                (input, VerboseErrorKind::Nom(ErrorKind::MapRes)),
+                (input, VerboseErrorKind::Context("a number in range")),
+            ],
+        })),
+    }
+}
+
+// A name made of letters and digits, like a valve or a monkey.
+pub fn name(input: &str) -> Res<'_, &str> {
+    context("a name", alphanumeric1)(input)
+}
+
+// A coordinate pair, like 498,4.
+pub fn point<'a, T: FromStr>(input: &'a str) -> Res<'a, (T, T)> {
+    context("a point like 498,4", separated_pair(int, char(','), int))(input)
+}
+
+// A coordinate triple, like 2,2,5.
+pub fn point3<'a, T: FromStr>(input: &'a str) -> Res<'a, (T, T, T)> {
+    let (rest, (x, _, y, _, z)) = context(
+        "a point like 2,2,5",
+        tuple((int, char(','), int, char(','), int)),
+    )(input)?;
+    Ok((rest, (x, y, z)))
+}
+
+// An inclusive range, like 2-4.
+pub fn range<'a, T: FromStr>(input: &'a str) -> Res<'a, (T, T)> {
+    context("a range like 2-4", separated_pair(int, char('-'), int))(input)
+}
+
+// Two ranges, like 2-4,6-8.
+#[allow(clippy::type_complexity)]
+pub fn ranges<'a, T: FromStr>(input: &'a str) -> Res<'a, ((T, T), (T, T))> {
+    separated_pair(range, char(','), range)(input)
+}
+
+// Points joined by arrows, like 498,4 -> 498,6 -> 496,6.
+pub fn path<'a, T: FromStr>(input: &'a str) -> Res<'a, Vec<(T, T)>> {
+    separated_list1(tag(" -> "), point)(input)
+}
+
+// A comma separated list of whatever the parser reads, like "79, 98" or "DD, II, BB".
+pub fn list<'a, T>(
+    parser: impl FnMut(&'a str) -> Res<'a, T>,
+) -> impl FnMut(&'a str) -> Res<'a, Vec<T>> {
+    separated_list1(alt((tag(", "), tag(","))), parser)
+}
+
+// Skip ahead to the next `prefix` and parse what comes after it. The words in between don't matter,
+// which suits sentences like "Each ore robot costs 4 ore." where only the numbers are wanted.
+pub fn after<'a, T>(
+    prefix: &'static str,
+    parser: impl FnMut(&'a str) -> Res<'a, T>,
+) -> impl FnMut(&'a str) -> Res<'a, T> {
+    preceded(pair(take_until(prefix), tag(prefix)), parser)
+}
+
+// The number in the next "key=value", like the x in "Sensor at x=2, y=18".
+// The key only counts when it's followed by '=', so "y" doesn't stop at the y in "Valve".
+pub fn field<'a, T: FromStr>(key: &'static str) -> impl FnMut(&'a str) -> Res<'a, T> {
+    move |input: &'a str| {
+        let value = input
+            .match_indices(key)
+            .map(|(i, _)| i + key.len())
+            .find(|&end| input[end..].starts_with('='));
+        match value {
+            Some(end) => int(&input[end + 1..]),
+            None => Err(nom::Err::Error(VerboseError::from_error_kind(
+                &input[input.len()..],
+                ErrorKind::TakeUntil,
+            ))),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #[allow(unused_imports)]
+    use super::*;
+
+    #[test]
+    fn shapes() {
+        assert_eq!(line(0, "-12", int::<i32>).unwrap(), -12);
+        assert_eq!(line(0, "498,4", point::<i32>).unwrap(), (498, 4));
+        assert_eq!(line(0, "2,2,5", point3::<i32>).unwrap(), (2, 2, 5));
+        assert_eq!(line(0, "2-4,6-8", ranges::<u32>).unwrap(), ((2, 4), (6, 8)));
+        assert_eq!(
+            line(0, "498,4 -> 498,6 -> 496,6", path::<i32>).unwrap(),
+            vec![(498, 4), (498, 6), (496, 6)]
+        );
+        assert_eq!(
+            line(0, "DD, II, BB", list(name)).unwrap(),
+            vec!["DD", "II", "BB"]
+        );
+
+        let sensor = "Sensor at x=2, y=18: closest beacon is at x=-2, y=15";
+        let fields = tuple((field("x"), field("y"), field("x"), field("y")));
+        assert_eq!(line(0, sensor, fields).unwrap(), (2, 18, -2, 15));
+
+        let robot = "Each obsidian robot costs 3 ore and 14 clay.";
+        let costs = terminated(
+            tuple((after("costs ", int), after("and ", int))),
+            tag(" clay."),
+        );
+        assert_eq!(line(0, robot, costs).unwrap(), (3u8, 14u8));
+    }
+
+    #[test]
+    fn errors_point_at_the_problem() {
+        let e = line(4, "2-4,6-x", ranges::<u32>).unwrap_err();
+        assert_eq!((e.line, e.column), (5, 7));
+        assert_eq!(e.message, "expected a number");
+
+        let e = line(0, "498,4 -> 498", path::<i32>).unwrap_err();
+        assert_eq!(e.column, 7);
+        assert_eq!(e.message, "expected the end of the line");
+
+        let step = tuple((tag("move "), int::<i32>, tag(" from "), int::<i32>));
+        let e = line(0, "move 1 frm 2", step).unwrap_err();
+        assert_eq!((e.column, e.message.as_str()), (7, "unexpected 'frm'"));
+
+        let e = line(0, "300,4", point::<u8>).unwrap_err();
+        assert_eq!(
+            (e.column, e.message.as_str()),
+            (1, "expected a number in range")
+        );
+
+        let e = line(0, "1,2 extra", point::<i32>).unwrap_err();
+        assert_eq!(e.column, 5);
+        assert_eq!(e.message, "expected the end of the line");
+
+        let e = line(0, "Sensor at x=2", field::<i32>("y")).unwrap_err();
+        assert_eq!(
+            (e.column, e.message.as_str()),
+            (14, "unexpected end of line")
+        );
+    }
+}
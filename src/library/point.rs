@@ -0,0 +1,274 @@
+// Points and directions, so days can add, step and measure positions instead of juggling tuples.
+// Like Grid, y grows downwards, so Up is (0, -1) and turning right from Up faces Right.
+
+use std::ops::{Add, AddAssign, Mul, Neg, Sub, SubAssign};
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Point2 {
+    pub x: i64,
+    pub y: i64,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Point3 {
+    pub x: i64,
+    pub y: i64,
+    pub z: i64,
+}
+
+// The four ways to face on a grid, clockwise from Up.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Direction {
+    Up,
+    Right,
+    Down,
+    Left,
+}
+
+impl Point2 {
+    pub const ORIGIN: Point2 = Point2 { x: 0, y: 0 };
+
+    pub const fn new(x: i64, y: i64) -> Self {
+        Point2 { x, y }
+    }
+
+    // Distance walking along the axes.
+    pub fn manhattan(self, other: Point2) -> i64 {
+        (self.x - other.x).abs() + (self.y - other.y).abs()
+    }
+
+    // Distance when diagonal steps count as one, like a king in chess.
+    pub fn chebyshev(self, other: Point2) -> i64 {
+        (self.x - other.x).abs().max((self.y - other.y).abs())
+    }
+
+    // Each coordinate as -1, 0 or 1. Turns a difference into a single step towards it.
+    pub fn signum(self) -> Self {
+        Point2::new(self.x.signum(), self.y.signum())
+    }
+
+    pub fn step(self, dir: Direction) -> Self {
+        self + dir.offset()
+    }
+
+    // The points sharing an edge with this one, clockwise from up.
+    pub fn neighbours4(self) -> [Point2; 4] {
+        Direction::ALL.map(|d| self.step(d))
+    }
+
+    // Every point around this one, diagonals included, clockwise from up.
+    pub fn neighbours8(self) -> [Point2; 8] {
+        [
+            (0, -1),
+            (1, -1),
+            (1, 0),
+            (1, 1),
+            (0, 1),
+            (-1, 1),
+            (-1, 0),
+            (-1, -1),
+        ]
+        .map(|(dx, dy)| Point2::new(self.x + dx, self.y + dy))
+    }
+}
+
+impl Point3 {
+    pub const ORIGIN: Point3 = Point3 { x: 0, y: 0, z: 0 };
+
+    pub const fn new(x: i64, y: i64, z: i64) -> Self {
+        Point3 { x, y, z }
+    }
+
+    pub fn manhattan(self, other: Point3) -> i64 {
+        (self.x - other.x).abs() + (self.y - other.y).abs() + (self.z - other.z).abs()
+    }
+
+    pub fn chebyshev(self, other: Point3) -> i64 {
+        (self.x - other.x)
+            .abs()
+            .max((self.y - other.y).abs())
+            .max((self.z - other.z).abs())
+    }
+
+    pub fn signum(self) -> Self {
+        Point3::new(self.x.signum(), self.y.signum(), self.z.signum())
+    }
+
+    // The points sharing a face with this one, like the cubes touching a cube.
+    pub fn neighbours6(self) -> [Point3; 6] {
+        [
+            (-1, 0, 0),
+            (1, 0, 0),
+            (0, -1, 0),
+            (0, 1, 0),
+            (0, 0, -1),
+            (0, 0, 1),
+        ]
+        .map(|(dx, dy, dz)| Point3::new(self.x + dx, self.y + dy, self.z + dz))
+    }
+}
+
+impl Direction {
+    pub const ALL: [Direction; 4] = [
+        Direction::Up,
+        Direction::Right,
+        Direction::Down,
+        Direction::Left,
+    ];
+
+    // The direction for one of the letters U/R/D/L. Inputs stick to one alphabet, so the compass points are a separate function,
+    // and a letter from the other one isn't mistaken for a direction.
+    pub fn from_letter(c: char) -> Option<Self> {
+        match c {
+            'U' => Some(Direction::Up),
+            'R' => Some(Direction::Right),
+            'D' => Some(Direction::Down),
+            'L' => Some(Direction::Left),
+            _ => None,
+        }
+    }
+
+    // The direction for one of the compass points N/E/S/W, with north being up.
+    pub fn from_compass(c: char) -> Option<Self> {
+        match c {
+            'N' => Some(Direction::Up),
+            'E' => Some(Direction::Right),
+            'S' => Some(Direction::Down),
+            'W' => Some(Direction::Left),
+            _ => None,
+        }
+    }
+
+    pub fn turn_right(self) -> Self {
+        Direction::ALL[(self as usize + 1) % 4]
+    }
+
+    pub fn turn_left(self) -> Self {
+        Direction::ALL[(self as usize + 3) % 4]
+    }
+
+    pub fn opposite(self) -> Self {
+        Direction::ALL[(self as usize + 2) % 4]
+    }
+
+    // A single step this way.
+    pub fn offset(self) -> Point2 {
+        match self {
+            Direction::Up => Point2::new(0, -1),
+            Direction::Right => Point2::new(1, 0),
+            Direction::Down => Point2::new(0, 1),
+            Direction::Left => Point2::new(-1, 0),
+        }
+    }
+}
+
+// Both points get the same component-wise arithmetic, so write it once.
+macro_rules! vector_ops {
+    ($point:ident { $($axis:ident),+ }) => {
+        impl Add for $point {
+            type Output = $point;
+
+            fn add(self, other: $point) -> $point {
+                $point { $($axis: self.$axis + other.$axis),+ }
+            }
+        }
+
+        impl Sub for $point {
+            type Output = $point;
+
+            fn sub(self, other: $point) -> $point {
+                $point { $($axis: self.$axis - other.$axis),+ }
+            }
+        }
+
+        impl Mul<i64> for $point {
+            type Output = $point;
+
+            fn mul(self, n: i64) -> $point {
+                $point { $($axis: self.$axis * n),+ }
+            }
+        }
+
+        impl Neg for $point {
+            type Output = $point;
+
+            fn neg(self) -> $point {
+                $point { $($axis: -self.$axis),+ }
+            }
+        }
+
+        impl AddAssign for $point {
+            fn add_assign(&mut self, other: $point) {
+                *self = *self + other;
+            }
+        }
+
+        impl SubAssign for $point {
+            fn sub_assign(&mut self, other: $point) {
+                *self = *self - other;
+            }
+        }
+    };
+}
+
+vector_ops!(Point2 { x, y });
+vector_ops!(Point3 { x, y, z });
+
+impl From<(i64, i64)> for Point2 {
+    fn from((x, y): (i64, i64)) -> Self {
+        Point2::new(x, y)
+    }
+}
+
+impl From<(i64, i64, i64)> for Point3 {
+    fn from((x, y, z): (i64, i64, i64)) -> Self {
+        Point3::new(x, y, z)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #[allow(unused_imports)]
+    use super::*;
+
+    #[test]
+    fn arithmetic() {
+        let a = Point2::new(2, 18);
+        let b = Point2::new(-2, 15);
+        assert_eq!(a + b, Point2::new(0, 33));
+        assert_eq!(a - b, Point2::new(4, 3));
+        assert_eq!(-b * 2, Point2::new(4, -30));
+        assert_eq!(a.manhattan(b), 7);
+        assert_eq!(a.chebyshev(b), 4);
+        assert_eq!((a - b).signum(), Point2::new(1, 1));
+
+        let mut c = Point3::new(1, 2, 3);
+        c += Point3::new(1, 1, 1);
+        assert_eq!(c, Point3::from((2, 3, 4)));
+        assert_eq!(c.manhattan(Point3::ORIGIN), 9);
+        assert_eq!(c.neighbours6().len(), 6);
+        assert!(c.neighbours6().iter().all(|n| n.manhattan(c) == 1));
+    }
+
+    #[test]
+    fn directions() {
+        use Direction::*;
+        assert_eq!(Up.turn_right(), Right);
+        assert_eq!(Up.turn_left(), Left);
+        assert_eq!(Left.turn_right().turn_right(), Right);
+        assert_eq!(Down.opposite(), Up);
+        assert_eq!(Direction::from_letter('L'), Some(Left));
+        assert_eq!(Direction::from_letter('N'), None);
+        assert_eq!(Direction::from_compass('N'), Some(Up));
+        assert_eq!(Direction::from_compass('L'), None);
+        assert_eq!(Direction::from_letter('x'), None);
+
+        let p = Point2::ORIGIN;
+        assert_eq!(p.step(Up), Point2::new(0, -1));
+        assert_eq!(p.neighbours4()[1], Point2::new(1, 0));
+        assert!(Direction::ALL
+            .iter()
+            .all(|&d| p.step(d).step(d.opposite()) == p));
+        assert!(p.neighbours8().iter().all(|n| n.chebyshev(p) == 1));
+    }
+}
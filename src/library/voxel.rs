@@ -0,0 +1,245 @@
+// A dense 3D grid of filled and empty cubes, for shapes built out of voxels like day 18's lava droplet.
+// The grid covers a box with its own bounds on each axis, so a long thin shape doesn't pay for a big cube around it.
+// The voxels themselves are a CubeSet sized to the box by default, with the grid adding the bounds and the flood fills on top.
+
+use super::bitset::{CubeSet, Points};
+use super::{graph, Point3};
+
+// Which voxels count as touching each other.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Connectivity {
+    // Sharing a face, so 6 neighbours.
+    Faces,
+    // Sharing a face, an edge or a corner, so 26 neighbours.
+    All,
+}
+
+impl Connectivity {
+    pub fn neighbours(self, p: Point3) -> Vec<Point3> {
+        match self {
+            Connectivity::Faces => p.neighbours6().to_vec(),
+            Connectivity::All => (-1..=1)
+                .flat_map(|x| {
+                    (-1..=1).flat_map(move |y| (-1..=1).map(move |z| Point3::new(x, y, z)))
+                })
+                .filter(|&d| d != Point3::ORIGIN)
+                .map(|d| p + d)
+                .collect(),
+        }
+    }
+}
+
+// The voxels can be kept in any set of points, so the same grid can be compared against one in a HashSet.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct VoxelGrid<S = CubeSet> {
+    // The corners of the box, both inclusive.
+    min: Point3,
+    max: Point3,
+    // The filled voxels, with room for the whole box.
+    cells: S,
+}
+
+impl<S: Points<Point3>> VoxelGrid<S> {
+    // An empty grid covering everything from `min` to `max`, inclusive.
+    pub fn new(min: Point3, max: Point3) -> Self {
+        VoxelGrid {
+            min,
+            max,
+            cells: S::with_bounds(min, max),
+        }
+    }
+
+    // The smallest grid that holds all of the points, with them filled in.
+    pub fn from_points(points: impl IntoIterator<Item = Point3>) -> Self {
+        let points = points.into_iter().collect::<Vec<_>>();
+        let Some(&first) = points.first() else {
+            return Self::new(Point3::ORIGIN, Point3::new(-1, -1, -1));
+        };
+
+        let (min, max) = points.iter().fold((first, first), |(min, max), p| {
+            (
+                Point3::new(min.x.min(p.x), min.y.min(p.y), min.z.min(p.z)),
+                Point3::new(max.x.max(p.x), max.y.max(p.y), max.z.max(p.z)),
+            )
+        });
+
+        let mut grid = Self::new(min, max);
+        for p in points {
+            grid.insert(p);
+        }
+        grid
+    }
+
+    pub fn min(&self) -> Point3 {
+        self.min
+    }
+
+    pub fn max(&self) -> Point3 {
+        self.max
+    }
+
+    pub fn in_bounds(&self, p: Point3) -> bool {
+        (self.min.x..=self.max.x).contains(&p.x)
+            && (self.min.y..=self.max.y).contains(&p.y)
+            && (self.min.z..=self.max.z).contains(&p.z)
+    }
+
+    // Whether the voxel is filled. Everything outside the bounds is empty.
+    pub fn contains(&self, p: Point3) -> bool {
+        self.cells.contains(&p)
+    }
+
+    // Fill a voxel, returning whether it was empty before. Panics if it's outside the bounds, rather than letting the box grow.
+    pub fn insert(&mut self, p: Point3) -> bool {
+        if !self.in_bounds(p) {
+            panic!(
+                "{p:?} is outside a grid from {:?} to {:?}",
+                self.min, self.max
+            );
+        }
+        self.cells.insert(p)
+    }
+
+    // How many voxels are filled.
+    pub fn len(&self) -> usize {
+        self.cells.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.cells.is_empty()
+    }
+
+    // Every position in the bounds, filled or not.
+    pub fn positions(&self) -> impl Iterator<Item = Point3> {
+        let (min, max) = (self.min, self.max);
+        (min.x..=max.x).flat_map(move |x| {
+            (min.y..=max.y).flat_map(move |y| (min.z..=max.z).map(move |z| Point3::new(x, y, z)))
+        })
+    }
+
+    // The filled voxels, ordered by x, then y, then z.
+    pub fn iter(&self) -> impl Iterator<Item = Point3> + '_ {
+        self.cells.iter()
+    }
+
+    // The empty space that can be reached from outside, moving through faces. The result has a layer of padding
+    // around the original bounds, so the flood can get all the way round the shape.
+    pub fn exterior(&self) -> Self {
+        let padding = Point3::new(1, 1, 1);
+        let mut outside = Self::new(self.min - padding, self.max + padding);
+
+        let air = graph::bfs_distances(outside.min, |&p| {
+            p.neighbours6()
+                .into_iter()
+                .filter(|&n| outside.in_bounds(n) && !self.contains(n))
+                .collect::<Vec<_>>()
+        });
+        for p in air.into_keys() {
+            outside.insert(p);
+        }
+        outside
+    }
+
+    // How many separate pieces the filled voxels make up.
+    pub fn components(&self, connectivity: Connectivity) -> usize {
+        let mut seen = Self::new(self.min, self.max);
+        let mut count = 0;
+
+        for p in self.iter() {
+            if seen.contains(p) {
+                continue;
+            }
+            count += 1;
+            let piece = graph::bfs_distances(p, |&q| {
+                connectivity
+                    .neighbours(q)
+                    .into_iter()
+                    .filter(|&n| self.contains(n))
+                    .collect::<Vec<_>>()
+            });
+            for q in piece.into_keys() {
+                seen.insert(q);
+            }
+        }
+
+        count
+    }
+
+    // The number of faces of filled voxels that don't touch another filled voxel, including those facing into pockets of trapped air.
+    pub fn surface_area(&self) -> usize {
+        self.iter()
+            .flat_map(|p| p.neighbours6())
+            .filter(|&n| !self.contains(n))
+            .count()
+    }
+
+    // Just the faces that can be reached from outside.
+    pub fn exterior_surface_area(&self) -> usize {
+        let outside = self.exterior();
+        self.iter()
+            .flat_map(|p| p.neighbours6())
+            .filter(|&n| outside.contains(n))
+            .count()
+    }
+
+    // The number of empty voxels sealed inside, which the outside can't reach.
+    pub fn enclosed_volume(&self) -> usize {
+        let outside = self.exterior();
+        self.positions()
+            .filter(|&p| !self.contains(p) && !outside.contains(p))
+            .count()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #[allow(unused_imports)]
+    use super::*;
+
+    #[test]
+    fn hollow_cube() {
+        // A 3x3x3 cube with the middle missing, so there's one voxel of air trapped inside.
+        let shell: VoxelGrid = VoxelGrid::from_points(
+            (0..3)
+                .flat_map(|x| (0..3).flat_map(move |y| (0..3).map(move |z| Point3::new(x, y, z))))
+                .filter(|&p| p != Point3::new(1, 1, 1)),
+        );
+        assert_eq!(shell.len(), 26);
+        assert_eq!(shell.surface_area(), 54 + 6);
+        assert_eq!(shell.exterior_surface_area(), 54);
+        assert_eq!(shell.enclosed_volume(), 1);
+        assert_eq!(shell.components(Connectivity::Faces), 1);
+        assert!(!shell.exterior().contains(Point3::new(1, 1, 1)));
+
+        // The same shape kept in a HashSet gives the same answers.
+        let hashed: VoxelGrid<std::collections::HashSet<Point3>> =
+            VoxelGrid::from_points(shell.iter());
+        assert_eq!(hashed.len(), 26);
+        assert_eq!(hashed.exterior_surface_area(), 54);
+        assert_eq!(hashed.enclosed_volume(), 1);
+    }
+
+    #[test]
+    fn bounds_and_pieces() {
+        // Only touching at a corner, and a long way apart on just one axis.
+        let grid: VoxelGrid = VoxelGrid::from_points([
+            Point3::new(0, 0, 0),
+            Point3::new(1, 1, 1),
+            Point3::new(0, 0, 20),
+        ]);
+        assert_eq!(
+            (grid.min(), grid.max()),
+            (Point3::ORIGIN, Point3::new(1, 1, 20))
+        );
+        assert_eq!(grid.positions().count(), 2 * 2 * 21);
+        assert_eq!(grid.surface_area(), 18);
+        assert_eq!(grid.components(Connectivity::Faces), 3);
+        assert_eq!(grid.components(Connectivity::All), 2);
+        assert_eq!(Connectivity::All.neighbours(Point3::ORIGIN).len(), 26);
+        assert!(!grid.contains(Point3::new(5, 5, 5)));
+
+        let empty: VoxelGrid = VoxelGrid::from_points([]);
+        assert!(empty.is_empty());
+        assert_eq!(empty.exterior_surface_area(), 0);
+    }
+}
@@ -1,79 +1,144 @@
 // Imports
+use rayon::prelude::*;
+use rayon::ThreadPoolBuilder;
 use std::env;
+use std::time::Instant;
 
 dirmod::all!(except template);
 
 fn main() {
-    // Use a pattern matching system to run a solution for a given day, or a wildcard to run all solutions.
-    // Get arguments from the command line.
-    let args: Vec<String> = env::args().collect();
-    let mut day: &str = "*";
-    // If there are no arguments (i.e size is 1), run all solutions.
-    if args.len() == 1 {
+    // Work out which days and parts to run from the command line, running every day if nothing was given.
+    let args: Vec<String> = env::args().skip(1).collect();
+    let options = match cli::parse(&args) {
+        Ok(cli::Command::Run(options)) => options,
+        Ok(cli::Command::Verify {
+            options,
+            data,
+            record,
+        }) => match verify::verify(&options, &data, record) {
+            Ok(true) => return,
+            Ok(false) => std::process::exit(1),
+            Err(error) => {
+                eprintln!("Error: {error}");
+                std::process::exit(2);
+            }
+        },
+        Ok(cli::Command::New { day, year }) => match scaffold::new_day(day, year) {
+            Ok(created) => {
+                for path in created {
+                    println!("Wrote {path}");
+                }
+                return;
+            }
+            Err(error) => {
+                eprintln!("Error: {error}");
+                std::process::exit(1);
+            }
+        },
+        Ok(cli::Command::Help) => {
+            print!("{}", cli::USAGE);
+            return;
+        }
+        Err(error) => {
+            eprintln!("Error: {error}\n\n{}", cli::USAGE);
+            std::process::exit(2);
+        }
+    };
+
+    if args.is_empty() {
         println!("No arguments provided, running all solutions.");
-    } else if args.len() == 2 {
-        // If there is an argument, use the first one as the day to run.
-        day = &args[1];
-    } else {
-        // If there are more than 2 arguments, print an error and exit.
-        println!("Too many arguments provided, exiting.");
-        std::process::exit(1);
     }
 
-    // Run the solution for the given day, or all days if we have a *.
-    match day {
-        "1" => solutions::day01::run(),
-        "2" => solutions::day02::run(),
-        "3" => solutions::day03::run(),
-        "4" => solutions::day04::run(),
-        "5" => solutions::day05::run(),
-        "6" => solutions::day06::run(),
-        "7" => solutions::day07::run(),
-        "8" => solutions::day08::run(),
-        "9" => solutions::day09::run(),
-        "10" => solutions::day10::run(),
-        "11" => solutions::day11::run(),
-        "12" => solutions::day12::run(),
-        "13" => solutions::day13::run(),
-        "14" => solutions::day14::run(),
-        "15" => solutions::day15::run(),
-        "16" => solutions::day16::run(),
-        "17" => solutions::day17::run(),
-        "18" => solutions::day18::run(),
-        "19" => solutions::day19::run(),
-        "20" => solutions::day20::run(),
-        "21" => solutions::day21::run(),
-        "22" => solutions::day22::run(),
-        "23" => solutions::day23::run(),
-        "24" => solutions::day24::run(),
-        "25" => solutions::day25::run(),
-        "*" => {
-            solutions::day01::run();
-            solutions::day02::run();
-            solutions::day03::run();
-            solutions::day04::run();
-            solutions::day05::run();
-            solutions::day06::run();
-            solutions::day07::run();
-            solutions::day08::run();
-            solutions::day09::run();
-            solutions::day10::run();
-            solutions::day11::run();
-            solutions::day12::run();
-            solutions::day13::run();
-            solutions::day14::run();
-            solutions::day15::run();
-            solutions::day16::run();
-            solutions::day17::run();
-            solutions::day18::run();
-            solutions::day19::run();
-            solutions::day20::run();
-            solutions::day21::run();
-            solutions::day22::run();
-            solutions::day23::run();
-            solutions::day24::run();
-            solutions::day25::run();
+    // Every day in the options has already been checked against the registry.
+    let days = options.days.iter().filter_map(|&d| solutions::find(d));
+
+    // Inputs given on the command line replace the day's data file. There's exactly one day when they are.
+    let files = (!options.inputs.is_empty()).then(|| {
+        runner::expand_inputs(&options.inputs).unwrap_or_else(|error| {
+            eprintln!("Error: {error}");
+            std::process::exit(2);
+        })
+    });
+    let batch = files.as_ref().is_some_and(|f| f.len() > 1);
+    let text = options.format == cli::Format::Text;
+
+    // Every day and input pair to run, in the order they're reported.
+    let work = days
+        .flat_map(|day| {
+            let paths = files
+                .clone()
+                .unwrap_or_else(|| vec![library::input_path(day.number())]);
+            paths.into_iter().map(move |path| (day, path))
+        })
+        .collect::<Vec<_>>();
+    let run = |(day, path): &(&'static dyn library::Day, String)| {
+        (
+            *day,
+            runner::try_run(*day, path, options.part, options.timeout),
+        )
+    };
+
+    let start = Instant::now();
+    let runs = match options.jobs {
+        // Days are independent, so run them on a pool. Collecting keeps the runs in day order, so they print just like a sequential run.
+        Some(jobs) => {
+            let pool = ThreadPoolBuilder::new()
+                .num_threads(jobs)
+                .build()
+                .expect("Could not start the thread pool");
+            let runs = pool.install(|| work.par_iter().map(run).collect::<Vec<_>>());
+            if text && !batch {
+                for (day, run) in &runs {
+                    runner::print_run(*day, run);
+                }
+            }
+            runs
         }
-        _ => println!("Invalid day provided, exiting."),
+        None => work
+            .iter()
+            .map(|w| {
+                let (day, run) = run(w);
+                // Print as we go, unless the output needs every run first.
+                if text && !batch {
+                    runner::print_run(day, &run);
+                }
+                (day, run)
+            })
+            .collect(),
+    };
+    let wall = start.elapsed();
+
+    match options.format {
+        cli::Format::Text if batch => runner::print_table(runs[0].0, runs.iter().map(|(_, r)| r)),
+        cli::Format::Text if options.time => runner::print_timings(&runs),
+        cli::Format::Text => {}
+        cli::Format::Json => runner::print_json(&runs),
+        cli::Format::Csv => runner::print_csv(&runs),
+    }
+
+    // A single run that worked speaks for itself. Otherwise say how the runs went, and fail if any of them did.
+    let failed = runs.iter().any(|(_, run)| run.failure.is_some());
+    if runs.len() > 1 || failed {
+        runner::print_summary(&runs);
+    }
+    // The point of running in parallel is finishing sooner than the days take added up, so show both.
+    // Each day's time is its own wall time, not CPU time, so days that fan out onto threads of their own count once.
+    // A day that timed out ran for at least its timeout, so it counts for that rather than nothing.
+    if let Some(jobs) = options.jobs {
+        let summed = runs
+            .iter()
+            .map(|(_, run)| match run.failure {
+                Some(runner::Failure::TimedOut(limit)) => limit,
+                _ => run.elapsed(),
+            })
+            .sum();
+        eprintln!(
+            "Wall time {} on {jobs} threads, {} summed over the days",
+            runner::format_duration(wall),
+            runner::format_duration(summed)
+        );
+    }
+    if failed {
+        std::process::exit(1);
     }
 }
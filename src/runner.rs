@@ -0,0 +1,594 @@
+// Runs registered days against their inputs, and reports the results.
+
+use crate::library::{Answer, Day, InputError, Part, RawInput};
+use serde::Serialize;
+use std::borrow::Borrow;
+use std::cell::Cell;
+use std::panic::{self, AssertUnwindSafe};
+use std::path::{Path, PathBuf};
+use std::sync::mpsc::{self, RecvTimeoutError};
+use std::sync::Once;
+use std::thread;
+use std::time::{Duration, Instant};
+
+// The outcome of running one day against one input.
+pub struct Run {
+    pub input: String,
+    // The parts that were asked for.
+    pub part: Part,
+    pub part1: Option<Answer>,
+    pub part2: Option<Answer>,
+    pub parse: Duration,
+    pub solve: Duration,
+    // Why the run has no answers, if it failed.
+    pub failure: Option<Failure>,
+}
+
+// The ways a day can fail to run.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Failure {
+    MissingInput,
+    // The input couldn't be read or parsed.
+    BadInput(InputError),
+    Panicked(String),
+    TimedOut(Duration),
+}
+
+impl Failure {
+    // Every kind of failure, in the order summaries list them.
+    pub const NAMES: [&'static str; 4] = ["panicked", "missing input", "bad input", "timed out"];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Failure::Panicked(_) => "panicked",
+            Failure::MissingInput => "missing input",
+            Failure::BadInput(_) => "bad input",
+            Failure::TimedOut(_) => "timed out",
+        }
+    }
+}
+
+impl Run {
+    // A run that failed before it had any answers.
+    fn failed(path: &str, part: Part, failure: Failure) -> Run {
+        Run {
+            input: path.to_string(),
+            part,
+            part1: None,
+            part2: None,
+            parse: Duration::ZERO,
+            solve: Duration::ZERO,
+            failure: Some(failure),
+        }
+    }
+
+    // The answer for a part, if it was solved. There's no answer for a part that doesn't exist.
+    pub fn answer(&self, part: u8) -> Option<&Answer> {
+        match part {
+            1 => self.part1.as_ref(),
+            2 => self.part2.as_ref(),
+            _ => None,
+        }
+    }
+
+    pub fn elapsed(&self) -> Duration {
+        self.parse + self.solve
+    }
+}
+
+// Parse and solve a single day against an input file.
+pub fn run(day: &dyn Day, path: &str, part: Part) -> Run {
+    let data = match RawInput::load(path) {
+        Ok(data) => data,
+        Err(error) => return Run::failed(path, part, Failure::BadInput(error)),
+    };
+
+    // Time parsing and solving separately, leaving out reading the file.
+    let start = Instant::now();
+    let input = match day.parse_input(&data) {
+        Ok(input) => input,
+        Err(error) => return Run::failed(path, part, Failure::BadInput(error.in_file(path))),
+    };
+    let parse = start.elapsed();
+
+    let start = Instant::now();
+    let (part1, part2) = day.solve_input(&*input, part);
+    let solve = start.elapsed();
+
+    Run {
+        input: path.to_string(),
+        part,
+        part1,
+        part2,
+        parse,
+        solve,
+        failure: None,
+    }
+}
+
+// Run a day like `run`, but catch a missing input or a panic so one broken day can't take the others down with it.
+// With a timeout, the day runs on a worker thread and is given up on once the time is up.
+pub fn try_run(day: &'static dyn Day, path: &str, part: Part, timeout: Option<Duration>) -> Run {
+    let failed = |failure| Run::failed(path, part, failure);
+
+    if path != "-" && !Path::new(path).is_file() {
+        return failed(Failure::MissingInput);
+    }
+
+    let Some(timeout) = timeout else {
+        return catch_run(day, path, part);
+    };
+
+    // A thread can't be killed, so a day that times out is left to finish in the background and its result is thrown away.
+    // It goes away when the process exits.
+    let (sender, receiver) = mpsc::channel();
+    let owned = path.to_string();
+    thread::spawn(move || {
+        // Nobody is listening any more if the day already timed out.
+        let _ = sender.send(catch_run(day, &owned, part));
+    });
+
+    match receiver.recv_timeout(timeout) {
+        Ok(run) => run,
+        Err(RecvTimeoutError::Timeout) => failed(Failure::TimedOut(timeout)),
+        Err(RecvTimeoutError::Disconnected) => {
+            failed(Failure::Panicked("worker thread stopped".to_string()))
+        }
+    }
+}
+
+thread_local! {
+    // Whether this thread is in the middle of running a day, so a panic here is caught and reported with the results.
+    static RUNNING_DAY: Cell<bool> = const { Cell::new(false) };
+}
+
+// Keep the default panic message out of the way while a day runs, since the panic is reported with the results anyway.
+// Days can run on several threads at once, so rather than swapping the hook in and out around each run, one hook is
+// installed that stays quiet only on threads running a day. Panics anywhere else still go to the previous hook.
+fn quiet_panics_in_days() {
+    static INSTALL: Once = Once::new();
+    INSTALL.call_once(|| {
+        let previous = panic::take_hook();
+        panic::set_hook(Box::new(move |info| {
+            if !RUNNING_DAY.with(Cell::get) {
+                previous(info);
+            }
+        }));
+    });
+}
+
+// Run a day, turning a panic into a failed run.
+fn catch_run(day: &dyn Day, path: &str, part: Part) -> Run {
+    quiet_panics_in_days();
+    RUNNING_DAY.with(|running| running.set(true));
+    let result = panic::catch_unwind(AssertUnwindSafe(|| run(day, path, part)));
+    RUNNING_DAY.with(|running| running.set(false));
+
+    result.unwrap_or_else(|payload| {
+        let message = payload
+            .downcast_ref::<&str>()
+            .map(|s| s.to_string())
+            .or_else(|| payload.downcast_ref::<String>().cloned())
+            .unwrap_or_else(|| "unknown panic".to_string());
+        Run::failed(path, part, Failure::Panicked(message))
+    })
+}
+
+// Print the stars for a single run.
+pub fn print_run(day: &dyn Day, run: &Run) {
+    println!("Day {:02}: {}", day.number(), day.title());
+    match &run.failure {
+        Some(Failure::MissingInput) => println!("Missing input: {}", run.input),
+        Some(Failure::BadInput(error)) => {
+            println!("Bad input: {error}");
+            // Point at the problem, unless it's about the whole file.
+            if error.line > 0 {
+                println!("{}", error.snippet());
+            }
+        }
+        Some(Failure::Panicked(message)) => println!("Panicked: {message}"),
+        Some(Failure::TimedOut(limit)) => println!("Timed out after {}", format_duration(*limit)),
+        None => {}
+    }
+    if let Some(p1) = &run.part1 {
+        println!("Star 1:{}", star(p1));
+    }
+    if let Some(p2) = &run.part2 {
+        println!("Star 2:{}", star(p2));
+    }
+    println!();
+}
+
+// Put an answer after its label. Multi-line answers start on the next line instead.
+fn star(answer: &Answer) -> String {
+    if answer.is_multiline() {
+        format!("\n{answer}")
+    } else {
+        format!(" {answer}")
+    }
+}
+
+// Print one row per input file for the same day, so different inputs can be compared side by side.
+pub fn print_table<'a>(day: &dyn Day, runs: impl IntoIterator<Item = &'a Run>) {
+    let rows = runs
+        .into_iter()
+        .map(|run| {
+            vec![
+                run.input.clone(),
+                cell(&run.part1),
+                cell(&run.part2),
+                match run.failure {
+                    Some(_) => "-".to_string(),
+                    None => format_duration(run.elapsed()),
+                },
+                run.failure.as_ref().map_or("ok", |f| f.name()).to_string(),
+            ]
+        })
+        .collect::<Vec<_>>();
+
+    println!("Day {:02}: {}", day.number(), day.title());
+    print_aligned(&["File", "Part 1", "Part 2", "Time", "Status"], &rows, &[3]);
+    println!();
+}
+
+// Print a count of the runs that worked and failed, then the failures one per line, to stderr so it stays out of machine-readable output.
+pub fn print_summary<R: Borrow<Run>>(runs: &[(&dyn Day, R)]) {
+    let failures = runs
+        .iter()
+        .filter_map(|(day, run)| Some((day, run.borrow(), run.borrow().failure.as_ref()?)))
+        .collect::<Vec<_>>();
+    let counts = Failure::NAMES
+        .iter()
+        .map(|&name| {
+            let count = failures.iter().filter(|(_, _, f)| f.name() == name).count();
+            format!("{count} {name}")
+        })
+        .collect::<Vec<_>>();
+
+    eprintln!("{} ok, {}", runs.len() - failures.len(), counts.join(", "));
+    for (day, run, failure) in &failures {
+        let detail = match failure {
+            Failure::MissingInput => format!("missing input {}", run.input),
+            // The error already says which file it was in.
+            Failure::BadInput(error) => format!("bad input: {error}"),
+            Failure::Panicked(message) => format!("panicked on {}: {message}", run.input),
+            Failure::TimedOut(limit) => format!(
+                "timed out on {} after {}",
+                run.input,
+                format_duration(*limit)
+            ),
+        };
+        eprintln!("  Day {:02}: {detail}", day.number());
+    }
+}
+
+// Print how long each day took to parse and solve, with the total for all of them at the bottom. Failed runs are left out.
+pub fn print_timings<R: Borrow<Run>>(runs: &[(&dyn Day, R)]) {
+    let runs = runs
+        .iter()
+        .filter(|(_, run)| run.borrow().failure.is_none())
+        .collect::<Vec<_>>();
+    let mut rows = runs
+        .iter()
+        .map(|(day, run)| {
+            let run = run.borrow();
+            vec![
+                format!("{:02}", day.number()),
+                day.title().to_string(),
+                format_duration(run.parse),
+                format_duration(run.solve),
+                format_duration(run.elapsed()),
+            ]
+        })
+        .collect::<Vec<_>>();
+
+    let parse = runs
+        .iter()
+        .map(|(_, run)| run.borrow().parse)
+        .sum::<Duration>();
+    let solve = runs
+        .iter()
+        .map(|(_, run)| run.borrow().solve)
+        .sum::<Duration>();
+    rows.push(vec![
+        String::new(),
+        "Total".to_string(),
+        format_duration(parse),
+        format_duration(solve),
+        format_duration(parse + solve),
+    ]);
+
+    print_aligned(
+        &["Day", "Title", "Parse", "Solve", "Total"],
+        &rows,
+        &[2, 3, 4],
+    );
+}
+
+// Print rows as columns padded to their widest cell. Columns listed in `right` are right-aligned, for numbers.
+pub fn print_aligned(header: &[&str], rows: &[Vec<String>], right: &[usize]) {
+    let header = header.iter().map(|h| h.to_string()).collect::<Vec<_>>();
+    let widths = (0..header.len())
+        .map(|i| {
+            rows.iter()
+                .chain([&header])
+                .map(|r| r[i].chars().count())
+                .max()
+                .unwrap_or(0)
+        })
+        .collect::<Vec<usize>>();
+
+    for row in [&header].into_iter().chain(rows) {
+        let line = row
+            .iter()
+            .enumerate()
+            .map(|(i, cell)| {
+                if right.contains(&i) {
+                    format!("{cell:>w$}", w = widths[i])
+                } else {
+                    format!("{cell:<w$}", w = widths[i])
+                }
+            })
+            .collect::<Vec<_>>();
+        println!("{}", line.join("  ").trim_end());
+    }
+}
+
+// One part of one run, flattened for machine-readable output.
+#[derive(Serialize)]
+pub struct Record<'a> {
+    pub day: u32,
+    pub part: u8,
+    pub input: &'a str,
+    // "ok", or the kind of failure.
+    pub status: &'static str,
+    pub answer: Option<&'a Answer>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+    // The parse and solve time of the whole run, shared by both of its parts.
+    pub parse_ms: f64,
+    pub solve_ms: f64,
+}
+
+// Flatten runs into one record per part asked for. Failed runs get a record per part too, without an answer.
+pub fn records<'a, R: Borrow<Run>>(runs: &'a [(&dyn Day, R)]) -> Vec<Record<'a>> {
+    runs.iter()
+        .flat_map(|(day, run)| {
+            let run = run.borrow();
+            run.part.numbers().iter().map(move |&part| Record {
+                day: day.number(),
+                part,
+                input: &run.input,
+                status: run.failure.as_ref().map_or("ok", |f| f.name()),
+                answer: run.answer(part),
+                error: match &run.failure {
+                    Some(Failure::BadInput(error)) => Some(error.to_string()),
+                    Some(Failure::Panicked(message)) => Some(message.clone()),
+                    _ => None,
+                },
+                parse_ms: run.parse.as_secs_f64() * 1_000.0,
+                solve_ms: run.solve.as_secs_f64() * 1_000.0,
+            })
+        })
+        .collect()
+}
+
+pub fn print_json<R: Borrow<Run>>(runs: &[(&dyn Day, R)]) {
+    println!("{}", serde_json::to_string_pretty(&records(runs)).unwrap());
+}
+
+pub fn print_csv<R: Borrow<Run>>(runs: &[(&dyn Day, R)]) {
+    println!("day,part,input,status,answer,parse_ms,solve_ms");
+    for r in records(runs) {
+        println!(
+            "{},{},{},{},{},{:.6},{:.6}",
+            r.day,
+            r.part,
+            csv_field(r.input),
+            r.status,
+            csv_field(&r.answer.map_or(String::new(), |a| a.to_string())),
+            r.parse_ms,
+            r.solve_ms
+        );
+    }
+}
+
+// Quote a CSV field if it has anything that would break the row apart.
+fn csv_field(s: &str) -> String {
+    if s.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", s.replace('"', "\"\""))
+    } else {
+        s.to_string()
+    }
+}
+
+// Multi-line answers don't fit in a table cell, so only say how big they are.
+pub fn cell(answer: &Option<Answer>) -> String {
+    match answer {
+        Some(Answer::Grid(rows)) => format!("<{} lines>", rows.len()),
+        Some(answer) => answer.to_string(),
+        None => "-".to_string(),
+    }
+}
+
+// Format a duration with a unit that keeps it readable.
+pub fn format_duration(d: Duration) -> String {
+    let micros = d.as_secs_f64() * 1_000_000.0;
+    if micros < 1_000.0 {
+        format!("{micros:.1}µs")
+    } else if micros < 1_000_000.0 {
+        format!("{:.2}ms", micros / 1_000.0)
+    } else {
+        format!("{:.2}s", micros / 1_000_000.0)
+    }
+}
+
+// Expand --input arguments into the files to run. Directories give all the files inside them, and `*`/`?` in the file name match like a shell glob.
+pub fn expand_inputs(inputs: &[String]) -> Result<Vec<String>, String> {
+    let mut files = Vec::new();
+
+    for input in inputs {
+        let path = Path::new(input);
+        let name = path.file_name().and_then(|n| n.to_str()).unwrap_or("");
+
+        let found = if input == "-" || path.is_file() {
+            vec![PathBuf::from(input)]
+        } else if path.is_dir() {
+            list_dir(path, "*")?
+        } else if name.contains(['*', '?']) {
+            let parent = match path.parent() {
+                Some(p) if !p.as_os_str().is_empty() => p,
+                _ => Path::new("."),
+            };
+            list_dir(parent, name)?
+        } else {
+            return Err(format!("Input '{input}' does not exist"));
+        };
+
+        if found.is_empty() {
+            return Err(format!("No input files match '{input}'"));
+        }
+        files.extend(found.into_iter().map(|p| p.to_string_lossy().into_owned()));
+    }
+
+    Ok(files)
+}
+
+// Files in a directory whose name matches a glob pattern, sorted by name.
+fn list_dir(dir: &Path, pattern: &str) -> Result<Vec<PathBuf>, String> {
+    let entries = dir
+        .read_dir()
+        .map_err(|e| format!("Could not read directory {}: {e}", dir.display()))?;
+
+    let mut files = entries
+        .filter_map(|entry| entry.ok().map(|e| e.path()))
+        .filter(|p| p.is_file())
+        .filter(|p| {
+            p.file_name()
+                .and_then(|n| n.to_str())
+                .is_some_and(|n| glob_match(pattern.as_bytes(), n.as_bytes()))
+        })
+        .collect::<Vec<PathBuf>>();
+
+    files.sort();
+    Ok(files)
+}
+
+// Match a name against a pattern where `*` is any run of characters and `?` is any single one.
+fn glob_match(pattern: &[u8], name: &[u8]) -> bool {
+    match (pattern.first(), name.first()) {
+        (None, None) => true,
+        (Some(b'*'), _) => {
+            glob_match(&pattern[1..], name) || (!name.is_empty() && glob_match(pattern, &name[1..]))
+        }
+        (Some(b'?'), Some(_)) => glob_match(&pattern[1..], &name[1..]),
+        (Some(p), Some(n)) if p == n => glob_match(&pattern[1..], &name[1..]),
+        _ => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #[allow(unused_imports)]
+    use super::*;
+
+    #[test]
+    fn failures_are_caught() {
+        let day = crate::solutions::find(1).unwrap();
+        let run = try_run(day, "testdata/nothing.txt", Part::Both, None);
+        assert_eq!(run.failure, Some(Failure::MissingInput));
+
+        // Day 1 can't make sense of another day's input, and says where.
+        let run = try_run(day, "testdata/day05.txt", Part::Both, None);
+        assert!(matches!(run.failure, Some(Failure::BadInput(ref e)) if e.line == 1));
+
+        // Day 9 checks every move, so a direction it doesn't know is reported on its line.
+        let day = crate::solutions::find(9).unwrap();
+        let path = std::env::temp_dir().join("fruitcake-day09-bad-direction.txt");
+        std::fs::write(&path, "R 4\nX 2\n").unwrap();
+        let run = try_run(day, path.to_str().unwrap(), Part::Both, None);
+        assert!(matches!(run.failure, Some(Failure::BadInput(ref e)) if e.line == 2));
+
+        // Every line of this parses for day 7, but with no root directory there's nothing to solve, and it panics instead.
+        let day = crate::solutions::find(7).unwrap();
+        let path = std::env::temp_dir().join("fruitcake-day07-no-root.txt");
+        std::fs::write(&path, "$ cd a\n$ ls\n14848514 b.txt\n").unwrap();
+        let run = try_run(day, path.to_str().unwrap(), Part::Both, None);
+        assert!(matches!(run.failure, Some(Failure::Panicked(_))));
+        assert!(run.part1.is_none());
+
+        // Day 19 takes far longer than a millisecond.
+        let limit = Duration::from_millis(1);
+        let day = crate::solutions::find(19).unwrap();
+        let run = try_run(day, "testdata/day19.txt", Part::Both, Some(limit));
+        assert_eq!(run.failure, Some(Failure::TimedOut(limit)));
+    }
+
+    #[test]
+    fn answers_by_part() {
+        let run = try_run(
+            crate::solutions::find(1).unwrap(),
+            "testdata/day01.txt",
+            Part::Both,
+            None,
+        );
+        assert_eq!(run.answer(1), Some(&Answer::Int(24000)));
+        assert_eq!(run.answer(2), Some(&Answer::Int(45000)));
+        assert_eq!(run.answer(0), None);
+        assert_eq!(run.answer(7), None);
+    }
+
+    #[test]
+    fn csv_quoting() {
+        assert_eq!(csv_field("CMZ"), "CMZ");
+        assert_eq!(csv_field("a,b"), "\"a,b\"");
+        assert_eq!(csv_field("#.\n.#"), "\"#.\n.#\"");
+        assert_eq!(csv_field("say \"hi\""), "\"say \"\"hi\"\"\"");
+    }
+
+    #[test]
+    fn globs() {
+        assert!(glob_match(b"*.txt", b"day15.txt"));
+        assert!(glob_match(b"day1?.txt", b"day15.txt"));
+        assert!(!glob_match(b"day1?.txt", b"day5.txt"));
+        assert!(!glob_match(b"*.txt", b"day15.txt.bak"));
+    }
+
+    #[test]
+    fn expand_directory_and_glob() {
+        // A directory of its own, so what's in it doesn't change when days are added to testdata.
+        let dir = std::env::temp_dir().join("fruitcake-expand-inputs");
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(dir.join("nested")).unwrap();
+        for name in ["day10.txt", "day01.txt", "day02.txt", "notes.md"] {
+            std::fs::write(dir.join(name), "").unwrap();
+        }
+        let names = |files: Vec<String>| {
+            files
+                .iter()
+                .map(|f| {
+                    Path::new(f)
+                        .file_name()
+                        .unwrap()
+                        .to_string_lossy()
+                        .into_owned()
+                })
+                .collect::<Vec<_>>()
+        };
+
+        // Every file in the directory, sorted, but not the directory inside it.
+        let all = expand_inputs(&[dir.to_string_lossy().into_owned()]).unwrap();
+        assert_eq!(
+            names(all),
+            ["day01.txt", "day02.txt", "day10.txt", "notes.md"]
+        );
+
+        let glob = dir.join("day0?.txt").to_string_lossy().into_owned();
+        assert_eq!(
+            names(expand_inputs(&[glob]).unwrap()),
+            ["day01.txt", "day02.txt"]
+        );
+
+        assert!(expand_inputs(&["testdata/nothing*.txt".to_string()]).is_err());
+    }
+}
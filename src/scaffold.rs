@@ -0,0 +1,157 @@
+// Creates the files for a new day from src/template.rs, and registers it so the runner and benches pick it up.
+
+use std::fs;
+use std::io::Write;
+use std::path::Path;
+
+const TEMPLATE: &str = "src/template.rs";
+const REGISTRY: &str = "src/solutions/mod.rs";
+
+// Fill in the template for a day. The puzzle URL and the DAY constant take the plain number, everything else the padded one.
+pub fn render(template: &str, day: u32, year: u32) -> String {
+    template
+        .replace(
+            "adventofcode.com/2022/",
+            &format!("adventofcode.com/{year}/"),
+        )
+        .replace("/day/XX", &format!("/day/{day}"))
+        .replace("u32 = XX;", &format!("u32 = {day};"))
+        .replace("XX", &format!("{day:02}"))
+}
+
+// Add a day to the SOLUTIONS list, keeping it in calendar order.
+pub fn register(registry: &str, day: u32) -> Result<String, String> {
+    let entry = format!("    &day{day:02}::Day{day:02},");
+    let mut lines = registry.lines().collect::<Vec<_>>();
+    if lines.contains(&entry.as_str()) {
+        return Err(format!("Day {day} is already registered in {REGISTRY}"));
+    }
+
+    let start = lines
+        .iter()
+        .position(|l| l.starts_with("pub static SOLUTIONS"))
+        .ok_or(format!("Could not find SOLUTIONS in {REGISTRY}"))?;
+    let end = start
+        + lines[start..]
+            .iter()
+            .position(|l| *l == "];")
+            .ok_or(format!("Could not find the end of SOLUTIONS in {REGISTRY}"))?;
+
+    // Entries are `    &dayNN::DayNN,`, so they sort by their text.
+    let at = (start + 1..end)
+        .find(|&i| lines[i] > entry.as_str())
+        .unwrap_or(end);
+    lines.insert(at, &entry);
+    Ok(lines.join("\n") + "\n")
+}
+
+// Create the solution and test data files for a day. Returns the paths that were written.
+// Everything is checked before anything is written, and if a write still fails, whatever was written is undone,
+// so a failure never leaves half a day behind for the next `new` to trip over.
+pub fn new_day(day: u32, year: u32) -> Result<Vec<String>, String> {
+    if !Path::new(REGISTRY).is_file() {
+        return Err(format!(
+            "Could not find {REGISTRY}, run this from the project root"
+        ));
+    }
+
+    let solution = format!("src/solutions/day{day:02}.rs");
+    let testdata = format!("testdata/day{day:02}.txt");
+    for path in [&solution, &testdata] {
+        if Path::new(path).exists() {
+            return Err(format!("{path} already exists"));
+        }
+        let dir = Path::new(path).parent().unwrap_or(Path::new("."));
+        if !dir.is_dir() {
+            return Err(format!("Could not find the {} directory", dir.display()));
+        }
+    }
+
+    let template =
+        fs::read_to_string(TEMPLATE).map_err(|e| format!("Could not read {TEMPLATE}: {e}"))?;
+    let original =
+        fs::read_to_string(REGISTRY).map_err(|e| format!("Could not read {REGISTRY}: {e}"))?;
+    let registry = register(&original, day)?;
+
+    let files = [
+        (solution.as_str(), render(&template, day, year)),
+        (testdata.as_str(), String::new()),
+    ];
+    let mut written = Vec::new();
+    let result = files
+        .iter()
+        .try_for_each(|(path, contents)| {
+            // Only ever create a file, so one that turned up since the check above isn't overwritten.
+            fs::OpenOptions::new()
+                .write(true)
+                .create_new(true)
+                .open(path)
+                .and_then(|mut file| {
+                    written.push(*path);
+                    file.write_all(contents.as_bytes())
+                })
+                .map_err(|e| format!("Could not write {path}: {e}"))
+        })
+        .and_then(|_| {
+            fs::write(REGISTRY, &registry).map_err(|e| {
+                // The registry may have been cut off partway through, so put the original back.
+                let _ = fs::write(REGISTRY, &original);
+                format!("Could not write {REGISTRY}: {e}")
+            })
+        });
+
+    if let Err(error) = result {
+        for path in written {
+            let _ = fs::remove_file(path);
+        }
+        return Err(error);
+    }
+
+    Ok(vec![solution, testdata, REGISTRY.to_string()])
+}
+
+#[cfg(test)]
+mod tests {
+    #[allow(unused_imports)]
+    use super::*;
+    use crate::library::{Answer, Day, Part, RawInput};
+
+    // The template is left out of the build, as it isn't a real day until it's rendered. Its placeholders are still valid Rust
+    // once XX is a number though, so build it as it is here, to catch it falling behind the library.
+    #[allow(dead_code)]
+    mod template {
+        const XX: u32 = 0;
+        include!("template.rs");
+    }
+
+    #[test]
+    fn template_compiles() {
+        let day: &dyn Day = &template::DayXX;
+        let input = day.parse_input(&RawInput::new("")).unwrap();
+        assert_eq!(
+            day.solve_input(&*input, Part::Both),
+            (Some(Answer::Int(0)), Some(Answer::Int(0)))
+        );
+    }
+
+    #[test]
+    fn render_template() {
+        let template = fs::read_to_string(TEMPLATE).unwrap();
+        let day = render(&template, 7, 2022);
+        assert!(day.starts_with("// https://adventofcode.com/2022/day/7\n"));
+        assert!(day.contains("pub struct Day07;"));
+        assert!(day.contains("const DAY: u32 = 7;"));
+        assert!(day.contains("testdata/day07.txt"));
+        assert!(!day.contains("XX"));
+    }
+
+    #[test]
+    fn register_in_order() {
+        let registry = fs::read_to_string(REGISTRY).unwrap();
+        assert!(register(&registry, 7).is_err());
+
+        // Pretend day 7 was never solved, and put it back.
+        let without = registry.replace("    &day07::Day07,\n", "");
+        assert_eq!(register(&without, 7).unwrap(), registry);
+    }
+}
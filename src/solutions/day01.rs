@@ -1,18 +1,13 @@
 // https://adventofcode.com/2022/day/1
 
-pub fn solve(data: &[String]) -> (i32, i32) {
-    // For the data, sum each set of numbers separated by an empty line and store them in an array.
-    let mut sums: Vec<i32> = vec![];
-    let mut sum: i32 = 0;
-
-    for line in data {
-        if line.is_empty() {
-            sums.push(sum);
-            sum = 0;
-        } else {
-            sum += line.parse::<i32>().unwrap();
-        }
-    }
+use crate::library::{parse_field, InputError, RawInput, Solution};
+
+pub fn solve(data: &[Vec<i32>]) -> (i32, i32) {
+    // Sum each elf's calories.
+    let mut sums = data
+        .iter()
+        .map(|elf| elf.iter().sum())
+        .collect::<Vec<i32>>();
 
     sums.sort();
     sums.reverse();
@@ -21,22 +16,46 @@ pub fn solve(data: &[String]) -> (i32, i32) {
     (sums[0], sums.iter().take(3).sum())
 }
 
-#[allow(dead_code)]
-pub fn run() {
-    let res = solve(&crate::library::read_file("data/day01.txt"));
-    println!("Day 01:\nStar 1: {}\nStar 2: {}\n", res.0, res.1);
+// Each elf's calories are a block of numbers, one per line.
+pub fn parse(input: &RawInput) -> Result<Vec<Vec<i32>>, InputError> {
+    input
+        .blocks()
+        .into_iter()
+        .map(|(start, block)| {
+            block
+                .lines()
+                .enumerate()
+                .map(|(i, line)| parse_field(start + i, line, line))
+                .collect()
+        })
+        .collect()
 }
 
-#[allow(dead_code)]
-pub fn benchmark(c: &mut criterion::Criterion) {
-    let data = parse(&crate::library::read_file("data/day01.txt"));
-    c.bench_function("Day 01 - solve:", |b| b.iter(|| solve(&data)));
-    c.bench_function("Day 01 - parse & solve:", |b| {
-        b.iter(|| {
-            let data = parse(&crate::library::read_file("data/day01.txt"));
-            solve(&data)
-        })
-    });
+pub struct Day01;
+
+impl Solution for Day01 {
+    const DAY: u32 = 1;
+    const TITLE: &'static str = "Calorie Counting";
+
+    type Input = Vec<Vec<i32>>;
+    type Part1 = i32;
+    type Part2 = i32;
+
+    fn parse(input: &RawInput) -> Result<Self::Input, InputError> {
+        parse(input)
+    }
+
+    fn part1(input: &Self::Input) -> i32 {
+        solve(input).0
+    }
+
+    fn part2(input: &Self::Input) -> i32 {
+        solve(input).1
+    }
+
+    fn solve(input: &Self::Input) -> (i32, i32) {
+        solve(input)
+    }
 }
 
 #[cfg(test)]
@@ -46,14 +65,14 @@ mod tests {
 
     #[test]
     fn part1() {
-        let res = solve(&crate::library::read_file("testdata/day01.txt"));
+        let res = solve(&parse(&RawInput::load("testdata/day01.txt").unwrap()).unwrap());
         assert_eq!(res.0, 24000);
         println!("Part 1: Expected: 24000, Actual: {}", res.0);
     }
 
     #[test]
     fn part2() {
-        let res = solve(&crate::library::read_file("testdata/day01.txt"));
+        let res = solve(&parse(&RawInput::load("testdata/day01.txt").unwrap()).unwrap());
         assert_eq!(res.1, 45000);
         println!("Part 2: Expected: 45000, Actual: {}", res.1);
     }
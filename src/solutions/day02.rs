@@ -1,46 +1,75 @@
 // https://adventofcode.com/2022/day/2
 
-pub fn solve(data: &[String]) -> (i32, i32) {
+use crate::library::{InputError, RawInput, Solution};
+
+pub fn solve(data: &[(i32, i32)]) -> (i32, i32) {
     // Get current score for p1.
     let mut p1: i32 = 0;
     let mut p2: i32 = 0;
 
-    // Get the ASCII value for character's 'A' and 'X'.
-    let a = b'A';
-    let x = b'X';
+    for &(c1, c2) in data {
+        // Done in i32, since c2 - c1 goes negative whenever the opponent's shape is ahead of ours.
+        p1 += c2 + 1 + ((c2 - c1 + 4) % 3) * 3;
+        p2 += c2 * 3 + ((c2 + c1 + 2) % 3) + 1;
+    }
 
-    for line in data {
-        // Destructure the line into 2 characters and remove any whitespace.
-        let (c1, c2) = line.split_at(1);
-        let c2 = c2.trim();
+    (p1, p2)
+}
 
-        // Get the ASCII value for the character and subtract a or x to get the relative position.
-        let c1 = c1.as_bytes()[0] - a;
-        let c2 = c2.as_bytes()[0] - x;
+// Each line is the opponent's shape and ours, like "A Y". Both become their relative position, 0 to 2.
+pub fn parse(input: &RawInput) -> Result<Vec<(i32, i32)>, InputError> {
+    let mut res = Vec::new();
 
-        p1 += (c2 + 1 + ((c2 - c1 + 4) % 3) * 3) as i32;
-        p2 += (c2 * 3 + ((c2 + c1 + 2) % 3) + 1) as i32;
+    for (i, line) in input.lines().enumerate() {
+        // Get the ASCII value for the character and subtract 'A' or 'X' to get the relative position.
+        let shape = |offset: usize, first: u8| match line.as_bytes().get(offset) {
+            Some(&c) if (first..first + 3).contains(&c) => Ok((c - first) as i32),
+            _ => Err(InputError::at(
+                i,
+                line,
+                offset,
+                format!("expected {} to {}", first as char, (first + 2) as char),
+            )),
+        };
+        let c1 = shape(0, b'A')?;
+        if line.as_bytes().get(1) != Some(&b' ') {
+            return Err(InputError::at(i, line, 1, "expected ' '"));
+        }
+        let c2 = shape(2, b'X')?;
+        if line.len() > 3 {
+            return Err(InputError::at(i, line, 3, "expected the end of the line"));
+        }
+        res.push((c1, c2));
     }
 
-    (p1, p2)
+    Ok(res)
 }
 
-#[allow(dead_code)]
-pub fn run() {
-    let res = solve(&crate::library::read_file("data/day02.txt"));
-    println!("Day 02:\nStar 1: {}\nStar 2: {}\n", res.0, res.1);
-}
+pub struct Day02;
+
+impl Solution for Day02 {
+    const DAY: u32 = 2;
+    const TITLE: &'static str = "Rock Paper Scissors";
+
+    type Input = Vec<(i32, i32)>;
+    type Part1 = i32;
+    type Part2 = i32;
 
-#[allow(dead_code)]
-pub fn benchmark(c: &mut criterion::Criterion) {
-    let data = parse(&crate::library::read_file("data/day02.txt"));
-    c.bench_function("Day 02 - solve:", |b| b.iter(|| solve(&data)));
-    c.bench_function("Day 02 - parse & solve:", |b| {
-        b.iter(|| {
-            let data = parse(&crate::library::read_file("data/day02.txt"));
-            solve(&data)
-        })
-    });
+    fn parse(input: &RawInput) -> Result<Self::Input, InputError> {
+        parse(input)
+    }
+
+    fn part1(input: &Self::Input) -> i32 {
+        solve(input).0
+    }
+
+    fn part2(input: &Self::Input) -> i32 {
+        solve(input).1
+    }
+
+    fn solve(input: &Self::Input) -> (i32, i32) {
+        solve(input)
+    }
 }
 
 #[cfg(test)]
@@ -50,14 +79,14 @@ mod tests {
 
     #[test]
     fn part1() {
-        let res = solve(&crate::library::read_file("testdata/day02.txt"));
+        let res = solve(&parse(&RawInput::load("testdata/day02.txt").unwrap()).unwrap());
         assert_eq!(res.0, 15);
         println!("Part 1: Expected: 15, Actual: {}", res.0);
     }
 
     #[test]
     fn part2() {
-        let res = solve(&crate::library::read_file("testdata/day02.txt"));
+        let res = solve(&parse(&RawInput::load("testdata/day02.txt").unwrap()).unwrap());
         assert_eq!(res.1, 12);
         println!("Part 2: Expected: 12, Actual: {}", res.1);
     }
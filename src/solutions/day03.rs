@@ -1,6 +1,9 @@
 // https://adventofcode.com/2022/day/3
 
-pub fn solve(data: &[String]) -> (i32, i32) {
+use crate::library::{InputError, RawInput, Solution};
+
+pub fn solve(data: &str) -> (i32, i32) {
+    let data = data.lines().collect::<Vec<_>>();
     let mut s1: i32 = 0;
     let mut s2: i32 = 0;
 
@@ -37,22 +40,32 @@ fn get_value(c: char) -> i32 {
     }
 }
 
-#[allow(dead_code)]
-pub fn run() {
-    let res = solve(&crate::library::read_file("data/day03.txt"));
-    println!("Day 03:\nStar 1: {}\nStar 2: {}\n", res.0, res.1);
-}
+pub struct Day03;
+
+impl Solution for Day03 {
+    const DAY: u32 = 3;
+    const TITLE: &'static str = "Rucksack Reorganization";
+
+    type Input = String;
+    type Part1 = i32;
+    type Part2 = i32;
+
+    fn parse(input: &RawInput) -> Result<Self::Input, InputError> {
+        // The rucksacks are only ever looked at as text, so keep the input whole and borrow its lines when solving.
+        Ok(input.as_str().to_string())
+    }
 
-#[allow(dead_code)]
-pub fn benchmark(c: &mut criterion::Criterion) {
-    let data = parse(&crate::library::read_file("data/day03.txt"));
-    c.bench_function("Day 03 - solve:", |b| b.iter(|| solve(&data)));
-    c.bench_function("Day 03 - parse & solve:", |b| {
-        b.iter(|| {
-            let data = parse(&crate::library::read_file("data/day03.txt"));
-            solve(&data)
-        })
-    });
+    fn part1(input: &Self::Input) -> i32 {
+        solve(input).0
+    }
+
+    fn part2(input: &Self::Input) -> i32 {
+        solve(input).1
+    }
+
+    fn solve(input: &Self::Input) -> (i32, i32) {
+        solve(input)
+    }
 }
 
 #[cfg(test)]
@@ -62,14 +75,14 @@ mod tests {
 
     #[test]
     fn part1() {
-        let res = solve(&crate::library::read_file("testdata/day03.txt"));
+        let res = solve(RawInput::load("testdata/day03.txt").unwrap().as_str());
         assert_eq!(res.0, 157);
         println!("Part 1: Expected: 157, Actual: {}", res.0);
     }
 
     #[test]
     fn part2() {
-        let res = solve(&crate::library::read_file("testdata/day03.txt"));
+        let res = solve(RawInput::load("testdata/day03.txt").unwrap().as_str());
         assert_eq!(res.1, 70);
         println!("Part 2: Expected: 70, Actual: {}", res.1);
     }
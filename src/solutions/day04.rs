@@ -1,19 +1,23 @@
 // https://adventofcode.com/2022/day/4
 
+use crate::library::parse::{self, ranges};
+use crate::library::{InputError, IntervalSet, RawInput, Solution};
+
 #[allow(clippy::type_complexity)]
-pub fn solve(data: &[((i32, i32), (i32, i32))]) -> (i32, i32) {
+pub fn solve(data: &[((i64, i64), (i64, i64))]) -> (i32, i32) {
     // Process the data
     data.iter()
-        .fold((0, 0), |(mut c1, mut c2), (first, second)| {
-            if (first.0 <= second.0 && first.1 >= second.1)
-                || (second.0 <= first.0 && second.1 >= first.1)
-            {
+        .fold((0, 0), |(mut c1, mut c2), &(first, second)| {
+            let first = IntervalSet::from(first);
+            let second = IntervalSet::from(second);
+            let overlap = first.intersection(&second);
+
+            // One fully contains the other when the overlap is the whole of either of them.
+            if overlap == first || overlap == second {
                 c1 += 1;
             }
 
-            if (first.0 <= second.0 && first.1 >= second.0)
-                || (second.0 <= first.0 && second.1 >= first.0)
-            {
+            if !overlap.is_empty() {
                 c2 += 1;
             }
 
@@ -21,41 +25,37 @@ pub fn solve(data: &[((i32, i32), (i32, i32))]) -> (i32, i32) {
         })
 }
 
-fn parse(data: &[String]) -> Vec<((i32, i32), (i32, i32))> {
-    data.iter()
-        .map(|x| {
-            let mut parts = x.split(',');
-            let mut first = parts.next().unwrap().split('-');
-            let mut second = parts.next().unwrap().split('-');
-            let first = (
-                first.next().unwrap().parse::<i32>().unwrap(),
-                first.next().unwrap().parse::<i32>().unwrap(),
-            );
-            let second = (
-                second.next().unwrap().parse::<i32>().unwrap(),
-                second.next().unwrap().parse::<i32>().unwrap(),
-            );
-            (first, second)
-        })
-        .collect()
+#[allow(clippy::type_complexity)]
+fn parse(input: &RawInput) -> Result<Vec<((i64, i64), (i64, i64))>, InputError> {
+    // Each line is a pair of ranges, like 2-4,6-8.
+    parse::lines(input.lines(), ranges)
 }
 
-#[allow(dead_code)]
-pub fn run() {
-    let res = solve(&parse(&crate::library::read_file("data/day04.txt")));
-    println!("Day 04:\nStar 1: {}\nStar 2: {}\n", res.0, res.1);
-}
+pub struct Day04;
 
-#[allow(dead_code)]
-pub fn benchmark(c: &mut criterion::Criterion) {
-    let data = parse(&crate::library::read_file("data/day04.txt"));
-    c.bench_function("Day 04 - solve:", |b| b.iter(|| solve(&data)));
-    c.bench_function("Day 04 - parse & solve:", |b| {
-        b.iter(|| {
-            let data = parse(&crate::library::read_file("data/day04.txt"));
-            solve(&data)
-        })
-    });
+impl Solution for Day04 {
+    const DAY: u32 = 4;
+    const TITLE: &'static str = "Camp Cleanup";
+
+    type Input = Vec<((i64, i64), (i64, i64))>;
+    type Part1 = i32;
+    type Part2 = i32;
+
+    fn parse(input: &RawInput) -> Result<Self::Input, InputError> {
+        parse(input)
+    }
+
+    fn part1(input: &Self::Input) -> i32 {
+        solve(input).0
+    }
+
+    fn part2(input: &Self::Input) -> i32 {
+        solve(input).1
+    }
+
+    fn solve(input: &Self::Input) -> (i32, i32) {
+        solve(input)
+    }
 }
 
 #[cfg(test)]
@@ -65,14 +65,14 @@ mod tests {
 
     #[test]
     fn part1() {
-        let res = solve(&parse(&crate::library::read_file("testdata/day04.txt")));
+        let res = solve(&parse(&RawInput::load("testdata/day04.txt").unwrap()).unwrap());
         assert_eq!(res.0, 2);
         println!("Part 1: Expected: 2, Actual: {}", res.0);
     }
 
     #[test]
     fn part2() {
-        let res = solve(&parse(&crate::library::read_file("testdata/day04.txt")));
+        let res = solve(&parse(&RawInput::load("testdata/day04.txt").unwrap()).unwrap());
         assert_eq!(res.1, 4);
         println!("Part 2: Expected: 4, Actual: {}", res.1);
     }
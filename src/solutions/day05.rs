@@ -1,5 +1,10 @@
 // https://adventofcode.com/2022/day/5
 
+use crate::library::parse::{self, int};
+use crate::library::{InputError, RawInput, Solution};
+use nom::bytes::complete::tag;
+use nom::sequence::{preceded, tuple};
+
 pub fn solve(mut cr: Vec<Vec<char>>, data: &[(i32, i32, i32)]) -> (String, String) {
     // Create a copy of the crates for the second part
     let mut cr2 = cr.clone();
@@ -38,66 +43,107 @@ pub fn solve(mut cr: Vec<Vec<char>>, data: &[(i32, i32, i32)]) -> (String, Strin
 }
 
 #[allow(clippy::type_complexity)]
-fn parse(data: &[String]) -> (Vec<Vec<char>>, Vec<(i32, i32, i32)>) {
-    // Data comes in a series of lines. Split into two different vectors of strings based on the first empty line.
-    let (header, data) = data.split_at(data.iter().position(|s| s.is_empty()).unwrap());
+fn parse(input: &RawInput) -> Result<(Vec<Vec<char>>, Vec<(i32, i32, i32)>), InputError> {
+    // Data comes in two blocks, the crates and then the moves.
+    let ((top, header), (start, moves)) = match input.blocks()[..] {
+        [header, moves] => (header, moves),
+        _ => {
+            return Err(InputError::file(
+                "expected the crates and the moves, separated by a blank line",
+            ))
+        }
+    };
 
-    // Get rid of the last line in the header, then reverse the header.
-    let header = header[0..header.len()]
-        .iter()
-        .rev()
-        .map(|s| s.to_string())
-        .collect::<Vec<String>>();
+    // Reverse the header, so the line numbering the stacks comes first. Keep each line's index in the file for errors.
+    let mut header = header
+        .lines()
+        .enumerate()
+        .map(|(i, line)| (top + i, line))
+        .collect::<Vec<_>>();
+    header.reverse();
 
     // Create the initial crates.
-    let mut cr = vec![vec![]; header[0].split_whitespace().count()];
-
-    // For each line in header except first line.
-    for line in header[1..].iter() {
-        line.chars()
-            .skip(1)
-            .step_by(4)
-            .enumerate()
-            .filter(|(_, c)| *c != ' ')
-            .for_each(|(i, c)| cr[i].push(c));
+    let mut cr = vec![vec![]; header[0].1.split_whitespace().count()];
+
+    // For each line in header except first line. A crate sits every 4 characters, starting at the second.
+    for &(index, line) in header[1..].iter() {
+        for (i, (offset, c)) in line.char_indices().skip(1).step_by(4).enumerate() {
+            if c == ' ' {
+                continue;
+            }
+            match cr.get_mut(i) {
+                Some(stack) => stack.push(c),
+                None => {
+                    return Err(InputError::at(
+                        index,
+                        line,
+                        offset,
+                        format!("expected at most {} stacks of crates", cr.len()),
+                    ))
+                }
+            }
+        }
     }
 
     // Read in the data and return a vector of (i32, i32, i32), of the numbers in the data.
     let mut numbers = Vec::new();
 
-    // Iterate over the vector of strings, skipping the first line.
-    for line in data.iter().skip(1) {
-        // Split the line into a vector of strings.
-        let split: Vec<&str> = line.split(' ').collect();
+    // Each line is "move A from B to C". Count lines from the top of the file for errors.
+    let step = || {
+        tuple((
+            preceded(tag("move "), int),
+            preceded(tag(" from "), int),
+            preceded(tag(" to "), int),
+        ))
+    };
+    for (i, line) in moves.lines().enumerate() {
+        let (count, from, to) = parse::line(start + i, line, step())?;
+
+        // Both ends of the move have to be stacks that are in the drawing.
+        for (stack, before) in [(from, " from "), (to, " to ")] {
+            if !(1..=cr.len() as i32).contains(&stack) {
+                let offset = line.find(before).map_or(0, |o| o + before.len());
+                return Err(InputError::at(
+                    start + i,
+                    line,
+                    offset,
+                    format!("expected a stack from 1 to {}", cr.len()),
+                ));
+            }
+        }
+        numbers.push((count, from, to));
+    }
+
+    Ok((cr, numbers))
+}
 
-        let a = split[1].parse::<i32>().unwrap();
-        let b = split[3].parse::<i32>().unwrap();
-        let c = split[5].parse::<i32>().unwrap();
+pub struct Day05;
 
-        // Push the tuple onto the vector.
-        numbers.push((a, b, c));
+impl Solution for Day05 {
+    const DAY: u32 = 5;
+    const TITLE: &'static str = "Supply Stacks";
+    // The crate drawing is read by column, so its spacing has to stay as it is.
+    const TRIM_WHITESPACE: bool = false;
+
+    type Input = (Vec<Vec<char>>, Vec<(i32, i32, i32)>);
+    type Part1 = String;
+    type Part2 = String;
+
+    fn parse(input: &RawInput) -> Result<Self::Input, InputError> {
+        parse(input)
     }
 
-    (cr, numbers)
-}
+    fn part1(input: &Self::Input) -> String {
+        solve(input.0.clone(), &input.1).0
+    }
 
-#[allow(dead_code)]
-pub fn run() {
-    let (header, data) = &parse(&crate::library::read_file("data/day05.txt"));
-    let res = solve(header.to_vec(), data);
-    println!("Day 05:\nStar 1: {}\nStar 2: {}\n", res.0, res.1);
-}
+    fn part2(input: &Self::Input) -> String {
+        solve(input.0.clone(), &input.1).1
+    }
 
-#[allow(dead_code)]
-pub fn benchmark(c: &mut criterion::Criterion) {
-    let data = parse(&crate::library::read_file("data/day05.txt"));
-    c.bench_function("Day 05 - solve:", |b| b.iter(|| solve(&data)));
-    c.bench_function("Day 05 - parse & solve:", |b| {
-        b.iter(|| {
-            let data = parse(&crate::library::read_file("data/day05.txt"));
-            solve(&data)
-        })
-    });
+    fn solve(input: &Self::Input) -> (String, String) {
+        solve(input.0.clone(), &input.1)
+    }
 }
 
 #[cfg(test)]
@@ -107,7 +153,7 @@ mod tests {
 
     #[test]
     fn part1() {
-        let (header, data) = &parse(&crate::library::read_file("testdata/day05.txt"));
+        let (header, data) = &parse(&RawInput::load("testdata/day05.txt").unwrap()).unwrap();
         let res = solve(header.to_vec(), data);
         assert_eq!(res.0, "CMZ");
         println!("Part 1: Expected: CMZ, Actual: {}", res.0);
@@ -115,9 +161,31 @@ mod tests {
 
     #[test]
     fn part2() {
-        let (header, data) = &parse(&crate::library::read_file("testdata/day05.txt"));
+        let (header, data) = &parse(&RawInput::load("testdata/day05.txt").unwrap()).unwrap();
         let res = solve(header.to_vec(), data);
         assert_eq!(res.1, "MCD");
         println!("Part 2: Expected: MCD, Actual: {}", res.1);
     }
+
+    #[test]
+    fn bad_stacks() {
+        let error = |text: &str| {
+            let e = parse(&RawInput::new(text)).unwrap_err();
+            (e.line, e.column, e.message)
+        };
+        let drawing = "    [D]\n[N] [C]\n 1   2\n\n";
+
+        assert_eq!(
+            error(&format!("{drawing}move 1 from 0 to 2")),
+            (5, 13, "expected a stack from 1 to 2".to_string())
+        );
+        assert_eq!(
+            error(&format!("{drawing}move 1 from 1 to 3")),
+            (5, 18, "expected a stack from 1 to 2".to_string())
+        );
+        assert_eq!(
+            error("[A] [B] [C]\n 1   2\n\nmove 1 from 1 to 2"),
+            (1, 10, "expected at most 2 stacks of crates".to_string())
+        );
+    }
 }
@@ -1,45 +1,63 @@
 // https://adventofcode.com/2022/day/6
 
-pub fn solve(data: &str) -> (i32, i32) {
-    let p1 = data
-        .as_bytes()
-        .windows(4)
-        .take_while(|c| not_unique(c))
-        .count() as i32;
-
-    let p2 = data
-        .as_bytes()
-        .windows(14)
-        .take_while(|c| not_unique(c))
-        .count() as i32;
+use crate::library::{InputError, RawInput, Solution};
+
+pub fn solve(data: &[u8]) -> (i32, i32) {
+    let p1 = data.windows(4).take_while(|c| not_unique(c)).count() as i32;
+
+    let p2 = data.windows(14).take_while(|c| not_unique(c)).count() as i32;
 
     (p1 + 4, p2 + 14)
 }
 
 fn not_unique(rng: &[u8]) -> bool {
-    let mut _mask: i32 = 0;
+    // One bit per lowercase letter, so a repeated letter sets the same bit twice.
+    let mut mask: u32 = 0;
     for c in rng {
-        _mask |= 1 << (*c - b'A');
+        mask |= 1 << (*c - b'a');
     }
-    _mask.count_ones() != rng.len() as u32
+    mask.count_ones() != rng.len() as u32
 }
 
-#[allow(dead_code)]
-pub fn run() {
-    let res = solve(&crate::library::read_file("data/day06.txt")[0]);
-    println!("Day 06:\nStar 1: {}\nStar 2: {}\n", res.0, res.1);
-}
+pub struct Day06;
+
+impl Solution for Day06 {
+    const DAY: u32 = 6;
+    const TITLE: &'static str = "Tuning Trouble";
+
+    type Input = Vec<u8>;
+    type Part1 = i32;
+    type Part2 = i32;
+
+    fn parse(input: &RawInput) -> Result<Self::Input, InputError> {
+        // The markers are found in the raw bytes of the first line.
+        let line = match input.as_bytes().split(|&b| b == b'\n').next() {
+            Some(line) if !line.is_empty() => line,
+            _ => return Err(InputError::file("the input is empty")),
+        };
+        // Each letter gets a bit of its own, so anything else in the signal can't be checked.
+        match line.iter().position(|b| !b.is_ascii_lowercase()) {
+            Some(offset) => Err(InputError::at(
+                0,
+                input.lines().next().unwrap_or_default(),
+                offset,
+                "expected only lowercase letters",
+            )),
+            None => Ok(line.to_vec()),
+        }
+    }
+
+    fn part1(input: &Self::Input) -> i32 {
+        solve(input).0
+    }
 
-#[allow(dead_code)]
-pub fn benchmark(c: &mut criterion::Criterion) {
-    let data = parse(&crate::library::read_file("data/day06.txt"));
-    c.bench_function("Day 06 - solve:", |b| b.iter(|| solve(&data)));
-    c.bench_function("Day 06 - parse & solve:", |b| {
-        b.iter(|| {
-            let data = parse(&crate::library::read_file("data/day06.txt"));
-            solve(&data)
-        })
-    });
+    fn part2(input: &Self::Input) -> i32 {
+        solve(input).1
+    }
+
+    fn solve(input: &Self::Input) -> (i32, i32) {
+        solve(input)
+    }
 }
 
 #[cfg(test)]
@@ -49,14 +67,14 @@ mod tests {
 
     #[test]
     fn part1() {
-        let res = solve(&crate::library::read_file("testdata/day06.txt")[0]);
+        let res = solve(&Day06::parse(&RawInput::load("testdata/day06.txt").unwrap()).unwrap());
         assert_eq!(res.0, 7);
         println!("Part 1: Expected 7: Actual: {}", res.0);
     }
 
     #[test]
     fn part2() {
-        let res = solve(&crate::library::read_file("testdata/day06.txt")[0]);
+        let res = solve(&Day06::parse(&RawInput::load("testdata/day06.txt").unwrap()).unwrap());
         assert_eq!(res.1, 19);
         println!("Part 2: Expected 19: Actual: {}", res.1);
     }
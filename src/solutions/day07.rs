@@ -1,45 +1,41 @@
 // https://adventofcode.com/2022/day/7
 
+use crate::library::{parse_field, InputError, RawInput, Solution};
 use std::collections::HashMap;
 use std::path::PathBuf;
 
-pub fn solve(data: &[String]) -> (i32, i32) {
+// The lines of the terminal that matter: moving between directories, and the size of each file listed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Line {
+    Out,
+    Into(String),
+    File(i32),
+}
+
+pub fn solve(data: &[Line]) -> (i32, i32) {
     // Create a vector to store the current directory-path we're in, hash-map to store the size of each directory.
     let mut path = Vec::new();
     let mut dir_sizes: HashMap<PathBuf, i32> = HashMap::new();
 
     for line in data {
-        // Need to skip if our op is a lookup or a dir op.
-        if line.starts_with("dir") || line.starts_with("$ ls") {
-            continue;
-        }
-
-        // Match for either the change directories, or on the file size + name. Can match an entire vector at once.
-        match line.split_whitespace().collect::<Vec<&str>>()[..] {
-            // Really cool syntax to match an array's parts all at once.
+        match line {
             // If we leave a directory, pop that out of our path vector.
-            ["$", "cd", ".."] => {
+            Line::Out => {
                 path.pop();
             }
 
-            // Need to do next part second because if you put it before other it causes issues due to the capture variable resulting in unreached statements in the match expression.
-            ["$", "cd", name] => {
-                // The random value can be captured into name variable. Kinda like a regex.
+            Line::Into(name) => {
                 path.push(name);
             }
 
-            // If we have a file, that is a size and then file_name we can just add the size to the hash-map with the key as a collection of the path.
-            [size, _name] => {
-                // Don't need _name, but use variable since it can be discarded and changed.
-                let size: i32 = size.parse().unwrap();
+            // If we have a file we can just add the size to the hash-map with the key as a collection of the path.
+            Line::File(size) => {
                 // For each path, that is for example we have something like dev/dir/a/b/c, we want to add the size at each step, so each directory always reflects the true size.
                 for i in 0..path.len() {
                     let p = PathBuf::from_iter(&path[..=i]); // Build a path from the path vector so we can use it as a key.
                     *dir_sizes.entry(p).or_insert(0) += size; // So access entry, if it doesn't exist init it to 0.
                 }
             }
-
-            _ => {}
         };
     }
 
@@ -63,22 +59,66 @@ pub fn solve(data: &[String]) -> (i32, i32) {
     (p1, p2)
 }
 
-#[allow(dead_code)]
-pub fn run() {
-    let res = solve(&crate::library::read_file("data/day07.txt"));
-    println!("Day 07:\nStar 1: {}\nStar 2: {}\n", res.0, res.1);
+pub fn parse(input: &RawInput) -> Result<Vec<Line>, InputError> {
+    let mut lines = Vec::new();
+
+    for (i, line) in input.lines().enumerate() {
+        // Need to skip if our op is a lookup or a dir op.
+        if line.starts_with("dir") || line.starts_with("$ ls") {
+            continue;
+        }
+
+        // Match for either the change directories, or on the file size + name. Can match an entire vector at once.
+        let parsed = match line.split_whitespace().collect::<Vec<&str>>()[..] {
+            // Really cool syntax to match an array's parts all at once.
+            ["$", "cd", ".."] => Line::Out,
+
+            // Need to do next part second because if you put it before other it causes issues due to the capture variable resulting in unreached statements in the match expression.
+            ["$", "cd", name] => Line::Into(name.to_string()),
+
+            // A file is a size and then its name, which we don't need.
+            [size, _name] => Line::File(parse_field(i, line, size)?),
+
+            _ => {
+                return Err(InputError::at(
+                    i,
+                    line,
+                    0,
+                    "expected a command, a directory or a file",
+                ))
+            }
+        };
+        lines.push(parsed);
+    }
+
+    Ok(lines)
 }
 
-#[allow(dead_code)]
-pub fn benchmark(c: &mut criterion::Criterion) {
-    let data = parse(&crate::library::read_file("data/day07.txt"));
-    c.bench_function("Day 07 - solve:", |b| b.iter(|| solve(&data)));
-    c.bench_function("Day 07 - parse & solve:", |b| {
-        b.iter(|| {
-            let data = parse(&crate::library::read_file("data/day07.txt"));
-            solve(&data)
-        })
-    });
+pub struct Day07;
+
+impl Solution for Day07 {
+    const DAY: u32 = 7;
+    const TITLE: &'static str = "No Space Left On Device";
+
+    type Input = Vec<Line>;
+    type Part1 = i32;
+    type Part2 = i32;
+
+    fn parse(input: &RawInput) -> Result<Self::Input, InputError> {
+        parse(input)
+    }
+
+    fn part1(input: &Self::Input) -> i32 {
+        solve(input).0
+    }
+
+    fn part2(input: &Self::Input) -> i32 {
+        solve(input).1
+    }
+
+    fn solve(input: &Self::Input) -> (i32, i32) {
+        solve(input)
+    }
 }
 
 #[cfg(test)]
@@ -88,14 +128,14 @@ mod tests {
 
     #[test]
     fn part1() {
-        let res = solve(&crate::library::read_file("testdata/day07.txt"));
+        let res = solve(&parse(&RawInput::load("testdata/day07.txt").unwrap()).unwrap());
         assert_eq!(res.0, 95437);
         println!("Part 1: Expected: 95437, Actual: {}", res.0);
     }
 
     #[test]
     fn part2() {
-        let res = solve(&crate::library::read_file("testdata/day07.txt"));
+        let res = solve(&parse(&RawInput::load("testdata/day07.txt").unwrap()).unwrap());
         assert_eq!(res.1, 24933642);
         println!("Part 2: Expected: 24933642, Actual: {}", res.1);
     }
@@ -1,110 +1,106 @@
 // https://adventofcode.com/2022/day/8
 
-pub fn solve(data: &Vec<Vec<i32>>) -> (i32, i32) {
-    // Grab the size of the tree vector.
-    let rows = data.len();
-    let cols = data[0].len();
+use crate::library::{Grid, InputError, RawInput, Solution};
 
-    // Create a 2D vector of size rows x cols filled with 0s.
-    let mut outside_vis = vec![vec![0; cols]; rows];
-    let mut scenic_vis = vec![vec![1; cols]; rows];
+pub fn solve(data: &Grid<i32>) -> (i32, i32) {
+    // Grab the size of the tree grid.
+    let (cols, rows) = (data.width(), data.height());
 
-    // Next we need a simple way to check if the given coordinates are valid, can use a closure for this, and ensure they are in range.
-    let is_valid = |x: i32, y: i32| x < rows as i32 && y < cols as i32 && x >= 0 && y >= 0;
+    // Create grids of the same size filled with 0s and 1s.
+    let mut outside_vis = Grid::new(cols, rows, 0);
+    let mut scenic_vis = Grid::new(cols, rows, 1);
+
+    // Every tree in a line from `start` to the edge, going in the direction `dir`.
+    let line = |start, dir| std::iter::once(start).chain(data.ray(start, dir));
 
     // Create a closure called process that will run a bunch of steps and process the data.
-    let mut process_outside = |mut x: usize, mut y: usize, factor: (i32, i32)| {
+    let mut process_outside = |start: (usize, usize), dir: (isize, isize)| {
         let mut max: i32 = -1; // Setting this to -1 will ensure that the first value is always greater than it, especially if it is a 0.
 
-        while is_valid(x as i32, y as i32) {
-            if data[x][y] > max {
-                outside_vis[x][y] = 1;
-                max = data[x][y];
+        for p in line(start, dir) {
+            if data[p] > max {
+                outside_vis[p] = 1;
+                max = data[p];
             }
-
-            x += factor.0 as usize;
-            y += factor.1 as usize;
         }
     };
 
-    let mut process_scenic = |mut x: usize, mut y: usize, factor: (i32, i32)| {
-        // Create an index variable to keep track of stuff for stack.
-        let mut index = 0;
-
+    let mut process_scenic = |start: (usize, usize), dir: (isize, isize)| {
         // Create a stack by using a vector that contains a tuple with initial value of (10, 0).
         let mut stack = vec![(10, 0)];
 
-        // While we have a valid index..
-        while is_valid(x as i32, y as i32) {
+        // Keep an index of how far along the line we are for the stack.
+        for (index, p) in line(start, dir).enumerate() {
+            let index = index as i32;
+
             // While stack's last element's first value is less than the value at the current index...
-            while stack.last().unwrap().0 < data[x][y] {
+            while stack.last().unwrap().0 < data[p] {
                 // Pop the last element off the stack.
                 stack.pop();
             }
 
             // Update the visibility of the current index by multiplying out with the index minus the stack's last element's second value.
-            scenic_vis[x][y] *= index - stack.last().unwrap().1;
+            scenic_vis[p] *= index - stack.last().unwrap().1;
             // Add to the stack the current value and the current index.
-            stack.push((data[x][y], index));
-
-            // Increment the x, y by the factor.
-            x += factor.0 as usize;
-            y += factor.1 as usize;
-            // Increment the index.
-            index += 1;
+            stack.push((data[p], index));
         }
     };
 
-    // For the range from 0 to rows... call the process closure with the current row and the current column, and a factor of 0, 1.
-    for i in 0..rows {
-        process_outside(i, 0, (0, 1));
-        process_outside(i, cols - 1, (0, -1));
-        process_scenic(i, 0, (0, 1));
-        process_scenic(i, cols - 1, (0, -1));
+    // Go along every row from both ends.
+    for y in 0..rows {
+        process_outside((0, y), (1, 0));
+        process_outside((cols - 1, y), (-1, 0));
+        process_scenic((0, y), (1, 0));
+        process_scenic((cols - 1, y), (-1, 0));
     }
 
-    // For the range from 0 to cols... call the process closure with the current row and the current column, and a factor of 1, 0.
-    for i in 0..cols {
-        process_outside(0, i, (1, 0));
-        process_outside(rows - 1, i, (-1, 0));
-        process_scenic(0, i, (1, 0));
-        process_scenic(rows - 1, i, (-1, 0));
+    // And down every column from both ends.
+    for x in 0..cols {
+        process_outside((x, 0), (0, 1));
+        process_outside((x, rows - 1), (0, -1));
+        process_scenic((x, 0), (0, 1));
+        process_scenic((x, rows - 1), (0, -1));
     }
 
-    // Sum the outside visibility by flattening the vector and summing the values.
-    let p1 = outside_vis.iter().flatten().sum();
-    let p2 = *scenic_vis.iter().flatten().max().unwrap();
+    // Sum the outside visibility, and find the best scenic score.
+    let p1 = outside_vis.iter().map(|(_, v)| v).sum();
+    let p2 = *scenic_vis.iter().map(|(_, v)| v).max().unwrap();
 
     (p1, p2)
 }
 
-pub fn parse(data: &[String]) -> Vec<Vec<i32>> {
-    data.iter()
-        .map(|digits| {
-            digits
-                .chars()
-                .map(|c| c.to_digit(10).unwrap() as i32)
-                .collect::<Vec<i32>>()
-        })
-        .collect()
+pub fn parse(input: &RawInput) -> Result<Grid<i32>, InputError> {
+    Grid::parse(input.lines(), |c| match c.to_digit(10) {
+        Some(d) => Ok(d as i32),
+        None => Err(format!("expected a digit, found '{c}'")),
+    })
 }
 
-#[allow(dead_code)]
-pub fn run() {
-    let res = solve(&parse(&crate::library::read_file("data/day08.txt")));
-    println!("Day 08:\nStar 1: {}\nStar 2: {}\n", res.0, res.1);
-}
+pub struct Day08;
+
+impl Solution for Day08 {
+    const DAY: u32 = 8;
+    const TITLE: &'static str = "Treetop Tree House";
+
+    type Input = Grid<i32>;
+    type Part1 = i32;
+    type Part2 = i32;
 
-#[allow(dead_code)]
-pub fn benchmark(c: &mut criterion::Criterion) {
-    let data = parse(&crate::library::read_file("data/day08.txt"));
-    c.bench_function("Day 08 - solve:", |b| b.iter(|| solve(&data)));
-    c.bench_function("Day 08 - parse & solve:", |b| {
-        b.iter(|| {
-            let data = parse(&crate::library::read_file("data/day08.txt"));
-            solve(&data)
-        })
-    });
+    fn parse(input: &RawInput) -> Result<Self::Input, InputError> {
+        parse(input)
+    }
+
+    fn part1(input: &Self::Input) -> i32 {
+        solve(input).0
+    }
+
+    fn part2(input: &Self::Input) -> i32 {
+        solve(input).1
+    }
+
+    fn solve(input: &Self::Input) -> (i32, i32) {
+        solve(input)
+    }
 }
 
 #[cfg(test)]
@@ -114,14 +110,14 @@ mod tests {
 
     #[test]
     fn part1() {
-        let res = solve(&parse(&crate::library::read_file("testdata/day08.txt")));
+        let res = solve(&parse(&RawInput::load("testdata/day08.txt").unwrap()).unwrap());
         assert_eq!(res.0, 21);
         println!("Part 1: Expected: 21, Actual: {}", res.0);
     }
 
     #[test]
     fn part2() {
-        let res = solve(&parse(&crate::library::read_file("testdata/day08.txt")));
+        let res = solve(&parse(&RawInput::load("testdata/day08.txt").unwrap()).unwrap());
         assert_eq!(res.1, 8);
         println!("Part 2: Expected: 8, Actual: {}", res.1);
     }
@@ -1,34 +1,28 @@
 // https://adventofcode.com/2022/day/9
 
 // Imports
-use std::collections::HashSet;
-
-// Define some constant directions for movement.
-const LEFT: (i32, i32) = (-1, 0);
-const RIGHT: (i32, i32) = (1, 0);
-const UP: (i32, i32) = (0, 1);
-const DOWN: (i32, i32) = (0, -1);
-
-// Rope structure that contains postion of the head and tail.
-struct Rope {
-    knots: Vec<(i32, i32)>,
-    visited_positions: HashSet<(i32, i32)>,
+use crate::library::bitset::Points;
+use crate::library::{parse_field, Direction, InputError, Point2, PointSet, RawInput, Solution};
+
+// Rope structure that contains postion of the head and tail. The visited positions can be kept in any set of points.
+struct Rope<S> {
+    knots: Vec<Point2>,
+    visited_positions: S,
 }
 
 // Implement the rope structure.
-impl Rope {
-    fn new(size: usize) -> Rope {
+impl<S: Points<Point2>> Rope<S> {
+    fn new(size: usize) -> Rope<S> {
         Rope {
-            knots: vec![(0, 0); size],
-            visited_positions: HashSet::new(),
+            knots: vec![Point2::ORIGIN; size],
+            visited_positions: S::default(),
         }
     }
 
     // Move the head, and then the tail accordingly.
-    fn move_dir(&mut self, dir: (i32, i32)) {
-        // Modify the head position by adding the direction vector.
-        self.knots[0].0 += dir.0;
-        self.knots[0].1 += dir.1;
+    fn move_dir(&mut self, dir: Direction) {
+        // Modify the head position by taking a step in the direction.
+        self.knots[0] = self.knots[0].step(dir);
 
         // Set up a loop to move all the knots.
         for i in 1..self.knots.len() {
@@ -37,40 +31,20 @@ impl Rope {
             // Tail is the last knot.
             let tail = self.knots[i];
 
-            if head.0.abs_diff(tail.0).max(head.1.abs_diff(tail.1)) > 1 {
-                match head.0.cmp(&tail.0) {
-                    std::cmp::Ordering::Less => self.knots[i].0 -= 1,
-                    std::cmp::Ordering::Greater => self.knots[i].0 += 1,
-                    std::cmp::Ordering::Equal => {}
-                }
-
-                match head.1.cmp(&tail.1) {
-                    std::cmp::Ordering::Less => self.knots[i].1 -= 1,
-                    std::cmp::Ordering::Greater => self.knots[i].1 += 1,
-                    std::cmp::Ordering::Equal => {}
-                }
+            // If the knots aren't touching, the tail takes one step towards the head on each axis.
+            if head.chebyshev(tail) > 1 {
+                self.knots[i] += (head - tail).signum();
             }
         }
     }
 
-    fn mark_visited(&mut self, pos: (i32, i32)) {
+    fn mark_visited(&mut self, pos: Point2) {
         self.visited_positions.insert(pos);
     }
 
     // Simulate according to a set of instructions.
-    fn simulate(&mut self, moves: Vec<(char, i32)>) {
-        for mv in moves {
-            // What happens if we split the moves by whitespace, and then filter out the empty strings?
-            let (dir, steps) = mv;
-
-            let dir = match dir {
-                'L' => LEFT,
-                'R' => RIGHT,
-                'U' => UP,
-                'D' => DOWN,
-                _ => panic!("Invalid direction!"),
-            };
-
+    fn simulate(&mut self, moves: &[(Direction, i32)]) {
+        for &(dir, steps) in moves {
             // Move that many times using the move_dir function.
             for _ in 0..steps {
                 self.move_dir(dir);
@@ -80,56 +54,87 @@ impl Rope {
     }
 }
 
-fn parse_movements(movements: &[String]) -> Vec<(char, i32)> {
+// Parsing function takes lines with format like: D #, where D is a direction and # is a number.
+pub fn parse(input: &RawInput) -> Result<Vec<(Direction, i32)>, InputError> {
     let mut parsed_movements = Vec::new();
 
-    // Iterate over the lines in the movements string
-    for line in movements {
-        // Parse the direction and number of steps from the line
-        let direction = line.chars().next().unwrap();
-        let steps = line[1..].trim().parse::<i32>().unwrap();
+    // Iterate over the lines in the input.
+    for (i, line) in input.lines().enumerate() {
+        let Some((direction, steps)) = line.split_once(' ') else {
+            return Err(InputError::at(
+                i,
+                line,
+                0,
+                "expected a direction and a number of steps",
+            ));
+        };
+
+        // Parse the direction and number of steps from the line.
+        let mut chars = direction.chars();
+        let direction = match (chars.next().and_then(Direction::from_letter), chars.next()) {
+            (Some(direction), None) => direction,
+            _ => {
+                return Err(InputError::at(
+                    i,
+                    line,
+                    0,
+                    format!("expected U, D, L or R, found '{direction}'"),
+                ))
+            }
+        };
+        let steps = parse_field(i, line, steps)?;
 
-        // Add the parsed movement to the vector
+        // Add the parsed movement to the vector.
         parsed_movements.push((direction, steps));
     }
 
-    parsed_movements
+    Ok(parsed_movements)
+}
+
+pub fn solve(data: &[(Direction, i32)]) -> (i32, i32) {
+    solve_with::<PointSet>(data)
 }
 
-pub fn solve(data: &[String]) -> (i32, i32) {
-    let mut rope = Rope::new(2);
-    let movements = parse_movements(data);
-    rope.simulate(movements);
+// Solve with the visited positions kept in a particular kind of set, so they can be compared.
+pub fn solve_with<S: Points<Point2>>(data: &[(Direction, i32)]) -> (i32, i32) {
+    let mut rope = Rope::<S>::new(2);
+    rope.simulate(data);
 
     let p1 = rope.visited_positions.len();
 
-    let mut rope = Rope::new(10);
-    let movements = parse_movements(data);
-    rope.simulate(movements);
+    let mut rope = Rope::<S>::new(10);
+    rope.simulate(data);
 
     let p2 = rope.visited_positions.len();
 
     (p1.try_into().unwrap(), p2.try_into().unwrap())
 }
 
-// Parsing function takes a vector of strings with format like: D #, where D is a direction and # is a number.
+pub struct Day09;
 
-#[allow(dead_code)]
-pub fn run() {
-    let res = solve(&crate::library::read_file("data/day09.txt"));
-    println!("Day 09:\nStar 1: {}\nStar 2: {}\n", res.0, res.1);
-}
+impl Solution for Day09 {
+    const DAY: u32 = 9;
+    const TITLE: &'static str = "Rope Bridge";
+
+    type Input = Vec<(Direction, i32)>;
+    type Part1 = i32;
+    type Part2 = i32;
+
+    fn parse(input: &RawInput) -> Result<Self::Input, InputError> {
+        parse(input)
+    }
 
-#[allow(dead_code)]
-pub fn benchmark(c: &mut criterion::Criterion) {
-    let data = parse(&crate::library::read_file("data/day09.txt"));
-    c.bench_function("Day 09 - solve:", |b| b.iter(|| solve(&data)));
-    c.bench_function("Day 09 - parse & solve:", |b| {
-        b.iter(|| {
-            let data = parse(&crate::library::read_file("data/day09.txt"));
-            solve(&data)
-        })
-    });
+    fn part1(input: &Self::Input) -> i32 {
+        solve(input).0
+    }
+
+    fn part2(input: &Self::Input) -> i32 {
+        solve(input).1
+    }
+
+    fn solve(input: &Self::Input) -> (i32, i32) {
+        solve(input)
+    }
 }
 
 #[cfg(test)]
@@ -139,15 +144,25 @@ mod tests {
 
     #[test]
     fn part1() {
-        let res = solve(&crate::library::read_file("testdata/day09.txt"));
+        let res = solve(&parse(&RawInput::load("testdata/day09.txt").unwrap()).unwrap());
         assert_eq!(res.0, 13);
         println!("Part 1: Expected: 13, Actual: {}", res.0);
     }
 
     #[test]
     fn part2() {
-        let res = solve(&crate::library::read_file("testdata/day09.txt"));
+        let res = solve(&parse(&RawInput::load("testdata/day09.txt").unwrap()).unwrap());
         assert_eq!(res.1, 1);
         println!("Part 2: Expected: 1, Actual: {}", res.1);
     }
+
+    #[test]
+    fn compass_points() {
+        // The rope only moves U/D/L/R, so a compass point is a mistake rather than another way of saying the same thing.
+        let e = parse(&RawInput::new("R 4\nN 4")).unwrap_err();
+        assert_eq!(
+            (e.line, e.message.as_str()),
+            (2, "expected U, D, L or R, found 'N'")
+        );
+    }
 }
@@ -1,5 +1,13 @@
 // https://adventofcode.com/2022/day/10
 
+use crate::library::parse::{self, int};
+use crate::library::{InputError, RawInput, Solution};
+use nom::branch::alt;
+use nom::bytes::complete::tag;
+use nom::character::complete::char;
+use nom::combinator::value;
+use nom::sequence::separated_pair;
+
 // Struct for an instruction.
 #[derive(Debug, Clone)]
 pub struct Instruction {
@@ -54,53 +62,48 @@ pub fn solve(data: &Vec<Instruction>) -> (i32, Vec<String>) {
     (p1, p2)
 }
 
-pub fn parse(data: &[String]) -> Vec<Instruction> {
-    let mut instructions = Vec::new();
-    for string in data {
-        if string == "noop" {
-            instructions.push(Instruction {
-                instruction: "noop".to_string(),
-                value: 0,
-            });
-        } else {
-            let mut parts = string.split_whitespace();
-
-            let instruction = match parts.next() {
-                Some(instruction) => instruction.to_string(),
-                None => continue,
-            };
-
-            let value = match parts.next() {
-                Some(value) => value.parse::<i32>(),
-                None => continue,
-            };
-
-            let value = value.unwrap();
-            instructions.push(Instruction { instruction, value });
-        }
+pub fn parse(input: &RawInput) -> Result<Vec<Instruction>, InputError> {
+    // Either "noop", or "addx" and the value to add. A noop doesn't have a value, so it gets 0.
+    let instruction = alt((
+        value(("noop", 0), tag("noop")),
+        separated_pair(tag("addx"), char(' '), int),
+    ));
+    let instructions = parse::lines(input.lines(), instruction)?;
+
+    Ok(instructions
+        .into_iter()
+        .map(|(instruction, value)| Instruction {
+            instruction: instruction.to_string(),
+            value,
+        })
+        .collect())
+}
+
+pub struct Day10;
+
+impl Solution for Day10 {
+    const DAY: u32 = 10;
+    const TITLE: &'static str = "Cathode-Ray Tube";
+
+    type Input = Vec<Instruction>;
+    type Part1 = i32;
+    type Part2 = Vec<String>;
+
+    fn parse(input: &RawInput) -> Result<Self::Input, InputError> {
+        parse(input)
     }
 
-    instructions
-}
+    fn part1(input: &Self::Input) -> i32 {
+        solve(input).0
+    }
 
-#[allow(dead_code)]
-pub fn run() {
-    let res = solve(&parse(&crate::library::read_file("data/day10.txt")));
-    // Create a string separated by newlines from the vector of strings.
-    let output = res.1.join("\n");
-    println!("Day 10:\nStar 1: {}\nStar 2:\n{}\n", res.0, output);
-}
+    fn part2(input: &Self::Input) -> Vec<String> {
+        solve(input).1
+    }
 
-#[allow(dead_code)]
-pub fn benchmark(c: &mut criterion::Criterion) {
-    let data = parse(&crate::library::read_file("data/day10.txt"));
-    c.bench_function("Day 10 - solve:", |b| b.iter(|| solve(&data)));
-    c.bench_function("Day 10 - parse & solve:", |b| {
-        b.iter(|| {
-            let data = parse(&crate::library::read_file("data/day10.txt"));
-            solve(&data)
-        })
-    });
+    fn solve(input: &Self::Input) -> (i32, Vec<String>) {
+        solve(input)
+    }
 }
 
 #[cfg(test)]
@@ -110,14 +113,14 @@ mod tests {
 
     #[test]
     fn part1() {
-        let res = solve(&parse(&crate::library::read_file("testdata/day10.txt")));
+        let res = solve(&parse(&RawInput::load("testdata/day10.txt").unwrap()).unwrap());
         assert_eq!(res.0, 13140);
         println!("Part 1: Expected: 13140, Actual: {}", res.0);
     }
 
     #[test]
     fn part2() {
-        let res = solve(&parse(&crate::library::read_file("testdata/day10.txt")));
+        let res = solve(&parse(&RawInput::load("testdata/day10.txt").unwrap()).unwrap());
         assert_eq!(res.1, res.1);
         println!("Part 2: Expected: EZFCHJAB, Actual: EZFCHJAB");
     }
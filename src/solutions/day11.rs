@@ -1,8 +1,24 @@
 // https://adventofcode.com/2022/day/11
 
+use crate::library::num::{Int, Number};
+use crate::library::parse::{self, after, int, list};
+use crate::library::{InputError, RawInput, Solution};
+use nom::branch::alt;
+use nom::bytes::complete::tag;
+use nom::character::complete::{char, one_of};
+use nom::combinator::{map, value};
+use nom::sequence::separated_pair;
+
+// The right hand side of an operation, which is either the old worry again or a number.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Operand {
+    Old,
+    Const(i64),
+}
+
 #[derive(Debug, Clone)]
 pub struct Operation {
-    op: String,
+    op: Operand,
     oper: char,
 }
 
@@ -15,12 +31,11 @@ pub struct Monkey {
 
 // Implement a new monkey.
 impl Monkey {
-    #[allow(dead_code)]
     pub fn new() -> Monkey {
         Monkey {
             worries: Vec::new(),
             operation: Operation {
-                op: String::new(),
+                op: Operand::Old,
                 oper: ' ',
             },
             test: vec![0, 0, 0],
@@ -28,19 +43,10 @@ impl Monkey {
     }
 }
 
-pub fn solve(monkeys: &mut Vec<Monkey>) -> (i64, i64) {
-    let mut p1_monkeys = monkeys.to_owned();
-    let p1 = calculate(&mut p1_monkeys, 20, String::from("1"));
-
-    let mut p2_monkeys = monkeys.to_owned();
-    let p2 = calculate(&mut p2_monkeys, 10_000, String::from("2"));
-
-    (p1, p2)
-}
-
-pub fn calculate(monkeys: &mut Vec<Monkey>, iterations: i64, part: String) -> i64 {
+// The worries are done in whatever number type N is, so an input that overflows can be caught, or run with big integers instead.
+pub fn calculate<N: Number>(monkeys: &[Monkey], iterations: i64, part: String) -> N {
     // Closure to calculate the worry of a monkey.
-    let calc_worry = |old: i64, new: i64, op: char| -> i64 {
+    let calc_worry = |old: N, new: N, op: char| -> N {
         match op {
             '+' => old + new,
             '*' => old * new,
@@ -48,43 +54,48 @@ pub fn calculate(monkeys: &mut Vec<Monkey>, iterations: i64, part: String) -> i6
         }
     };
 
+    // The items each monkey is holding, by how worrying they are.
+    let mut worries: Vec<Vec<N>> = monkeys
+        .iter()
+        .map(|m| m.worries.iter().map(|&w| N::from(w)).collect())
+        .collect();
+
     let mut inspected = vec![0; monkeys.len()];
-    let product = monkeys.iter().fold(1, |acc, m| acc * m.test[0]);
+    let product = monkeys
+        .iter()
+        .fold(N::from(1), |acc, m| acc * N::from(m.test[0]));
 
     // Need to do iterations times.
     for _ in 0..iterations {
         for i in 0..monkeys.len() {
-            // Clone the worries of the current monkey.
-            let worries = monkeys[i].worries.clone();
+            // Take the worries of the current monkey, leaving it with none.
+            let current = std::mem::take(&mut worries[i]);
 
             // Iterate over the worries of the current monkey.
-            for w in worries {
-                let op = if monkeys[i].operation.op == "old" {
-                    w
-                } else {
-                    monkeys[i].operation.op.parse::<i64>().unwrap()
+            for w in current {
+                let op = match monkeys[i].operation.op {
+                    Operand::Old => w.clone(),
+                    Operand::Const(n) => N::from(n),
                 };
 
                 let mut new_worry = calc_worry(w, op, monkeys[i].operation.oper);
 
                 if part.as_str() == "1" {
-                    new_worry /= 3;
+                    new_worry = new_worry / N::from(3);
                 } else if part.as_str() == "2" {
-                    new_worry %= product;
+                    new_worry = new_worry % product.clone();
                 }
 
-                if new_worry % monkeys[i].test[0] == 0 {
+                if new_worry.clone() % N::from(monkeys[i].test[0]) == N::from(0) {
                     let next_monkey = monkeys[i].test[1] as usize;
-                    monkeys[next_monkey].worries.push(new_worry);
+                    worries[next_monkey].push(new_worry);
                 } else {
                     let next_monkey = monkeys[i].test[2] as usize;
-                    monkeys[next_monkey].worries.push(new_worry);
+                    worries[next_monkey].push(new_worry);
                 }
 
                 inspected[i] += 1;
             }
-
-            monkeys[i].worries.clear();
         }
     }
 
@@ -92,73 +103,97 @@ pub fn calculate(monkeys: &mut Vec<Monkey>, iterations: i64, part: String) -> i6
     inspected.sort();
 
     // Return the product of the highest and 2nd highest values.
-    inspected[inspected.len() - 1] * inspected[inspected.len() - 2]
+    N::from(inspected[inspected.len() - 1]) * N::from(inspected[inspected.len() - 2])
 }
 
-pub fn parse(data: &[String]) -> Vec<Monkey> {
-    // Parser will parse the data into a vector of monkeys.
+pub fn parse(input: &RawInput) -> Result<Vec<Monkey>, InputError> {
+    // Parser will parse the data into a vector of monkeys, one per block of lines.
     let mut monkeys: Vec<Monkey> = Vec::new();
-    let mut line_num = 0;
-
-    for line in data {
-        if line_num == 6 {
-            line_num = 0;
-            continue;
+    // Where each throw goes, so they can be checked once we know how many monkeys there are.
+    let mut throws = Vec::new();
+
+    for (start, block) in input.blocks() {
+        let lines = block.lines().collect::<Vec<_>>();
+        let [_, items, operation, divisor, if_true, if_false] = lines[..] else {
+            return Err(InputError::at(
+                start,
+                lines[0],
+                0,
+                "expected a monkey with 6 lines",
+            ));
+        };
+        let mut monkey = Monkey::new();
+
+        // Starting items: 79, 98
+        monkey.worries = parse::line(start + 1, items, after("Starting items: ", list(int)))?;
+
+        // Operation: new = old * 19, where the right hand side can also be old.
+        let (oper, op) = parse::line(
+            start + 2,
+            operation,
+            after(
+                "new = old ",
+                separated_pair(
+                    one_of("+*"),
+                    char(' '),
+                    alt((value(Operand::Old, tag("old")), map(int, Operand::Const))),
+                ),
+            ),
+        )?;
+        monkey.operation = Operation { op, oper };
+
+        // The divisor, then which monkeys to throw to.
+        let tests = [
+            (divisor, "divisible by "),
+            (if_true, "throw to monkey "),
+            (if_false, "throw to monkey "),
+        ];
+        for (i, (line, prefix)) in tests.into_iter().enumerate() {
+            monkey.test[i] = parse::line(start + 3 + i, line, after(prefix, int))?;
+            if i > 0 {
+                throws.push((start + 3 + i, line, monkey.test[i]));
+            }
         }
 
-        let monkeys_len = monkeys.len();
+        monkeys.push(monkey);
+    }
 
-        match line_num {
-            0 => {
-                monkeys.push(Monkey::new());
-            }
+    // Every item has to land on a monkey that's actually there.
+    for (index, line, target) in throws {
+        if !(0..monkeys.len() as i64).contains(&target) {
+            return Err(InputError::at(
+                index,
+                line,
+                line.rfind(' ').map_or(0, |o| o + 1),
+                format!("expected a monkey from 0 to {}", monkeys.len() - 1),
+            ));
+        }
+    }
 
-            1 => {
-                let split = line.trim().split(' ').collect::<Vec<&str>>();
-                split.iter().skip(2).for_each(|s| {
-                    monkeys[monkeys_len - 1]
-                        .worries
-                        .push(s.replace(',', "").parse::<i64>().unwrap());
-                });
-            }
+    Ok(monkeys)
+}
 
-            2 => {
-                let split = line.trim().split(' ').collect::<Vec<&str>>();
-                monkeys[monkeys_len - 1].operation.op = split[5].to_string();
-                monkeys[monkeys_len - 1].operation.oper = split[4].chars().next().unwrap();
-            }
+pub struct Day11;
 
-            3 | 4 | 5 => {
-                let split = line.trim().split(' ').collect::<Vec<&str>>();
-                monkeys[monkeys_len - 1].test[line_num - 3] =
-                    split[split.len() - 1].parse::<i64>().unwrap();
-            }
+impl Solution for Day11 {
+    const DAY: u32 = 11;
+    const TITLE: &'static str = "Monkey in the Middle";
 
-            _ => panic!("Invalid line number!"),
-        }
+    type Input = Vec<Monkey>;
+    type Part1 = Int;
+    type Part2 = Int;
 
-        line_num += 1;
+    fn parse(input: &RawInput) -> Result<Self::Input, InputError> {
+        parse(input)
     }
 
-    monkeys
-}
-
-#[allow(dead_code)]
-pub fn run() {
-    let res = solve(&mut parse(&crate::library::read_file("data/day11.txt")));
-    println!("Day 11:\nStar 1: {}\nStar 2: {}\n", res.0, res.1);
-}
+    fn part1(input: &Self::Input) -> Int {
+        calculate(input, 20, String::from("1"))
+    }
 
-#[allow(dead_code)]
-pub fn benchmark(c: &mut criterion::Criterion) {
-    let data = parse(&crate::library::read_file("data/day11.txt"));
-    c.bench_function("Day 11 - solve:", |b| b.iter(|| solve(&data)));
-    c.bench_function("Day 11 - parse & solve:", |b| {
-        b.iter(|| {
-            let data = parse(&crate::library::read_file("data/day11.txt"));
-            solve(&data)
-        })
-    });
+    fn part2(input: &Self::Input) -> Int {
+        calculate(input, 10_000, String::from("2"))
+    }
 }
 
 #[cfg(test)]
@@ -168,15 +203,25 @@ mod tests {
 
     #[test]
     fn part1() {
-        let res = solve(&mut parse(&crate::library::read_file("testdata/day11.txt")));
-        assert_eq!(res.0, 10605);
+        let res = Day11::solve(&parse(&RawInput::load("testdata/day11.txt").unwrap()).unwrap());
+        assert_eq!(res.0, Int::from(10605));
         println!("Part 1: Expected: 10605, Actual: {}", res.0);
     }
 
     #[test]
     fn part2() {
-        let res = solve(&mut parse(&crate::library::read_file("testdata/day11.txt")));
-        assert_eq!(res.1, 2713310158);
+        let res = Day11::solve(&parse(&RawInput::load("testdata/day11.txt").unwrap()).unwrap());
+        assert_eq!(res.1, Int::from(2713310158i64));
         println!("Part 2: Expected: 2713310158, Actual: {}", res.1);
     }
+
+    #[test]
+    fn bad_throw() {
+        let text = std::fs::read_to_string("testdata/day11.txt").unwrap();
+        let e = parse(&RawInput::new(text.replacen("monkey 3", "monkey 4", 1))).unwrap_err();
+        assert_eq!(
+            (e.line, e.column, e.message.as_str()),
+            (6, 31, "expected a monkey from 0 to 3")
+        );
+    }
 }
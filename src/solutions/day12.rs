@@ -1,58 +1,75 @@
 // https://adventofcode.com/2022/day/12
 
-use pathfinding::prelude::bfs; // https://docs.rs/pathfinding/latest/pathfinding/directed/bfs/fn.bfs.html
-use pathfinding::prelude::Matrix; // https://docs.rs/pathfinding/latest/pathfinding/matrix/index.html
+use crate::library::graph::bfs;
+use crate::library::{Grid, InputError, RawInput, Solution};
 
-pub fn solve(data: &str) -> (i32, i32) {
-    // Parse the input data into a grid of characters.
-    let mut grid = Matrix::from_rows(data.lines().map(str::bytes)).unwrap();
+pub fn solve(data: &Grid<u8>) -> (i32, i32) {
+    let mut grid = data.clone();
 
     // Find the start and end points.
-    let start = grid.indices().find(|&i| grid[i] == b'S').unwrap();
-    let end = grid.indices().find(|&i| grid[i] == b'E').unwrap();
+    let start = grid.position(|&c| c == b'S').unwrap();
+    let end = grid.position(|&c| c == b'E').unwrap();
 
     // Replace the start and end points with values of 'a' and 'z' as bytes.
     grid[start] = b'a';
     grid[end] = b'z';
 
-    // Run the BFS search, from the start to the end and get the path length.
-    let (g, s, e) = &(grid, start, end);
+    // Run the BFS search, from the start to the end and get the number of steps.
+    let g = &grid;
 
     let p1 = bfs(
-        s,
-        |&i| g.neighbours(i, false).filter(move |&j| g[j] <= g[i] + 1),
-        |&i| i == *e,
+        start,
+        |&i| g.neighbours4(i).filter(move |&j| g[j] <= g[i] + 1),
+        |&i| i == end,
     )
     .unwrap()
-    .len() as i32;
+    .cost as i32;
 
+    // For part 2 search backwards from the end, climbing down at most one step at a time, until we reach any 'a'.
     let p2 = bfs(
-        e,
-        |&i| g.neighbours(i, false).filter(move |&j| g[i] <= g[j] + 1),
+        end,
+        |&i| g.neighbours4(i).filter(move |&j| g[i] <= g[j] + 1),
         |&i| g[i] == b'a',
     )
     .unwrap()
-    .len() as i32;
+    .cost as i32;
 
-    (p1 - 1, p2 - 1)
+    (p1, p2)
 }
 
-#[allow(dead_code)]
-pub fn run() {
-    let res = solve(&crate::library::read_file("data/day12.txt").join("\n"));
-    println!("Day 12:\nStar 1: {}\nStar 2: {}\n", res.0, res.1);
+// Parse the input data into a grid of heights, still as the letters.
+pub fn parse(input: &RawInput) -> Result<Grid<u8>, InputError> {
+    Grid::parse(input.lines(), |c| match c {
+        'a'..='z' | 'S' | 'E' => Ok(c as u8),
+        _ => Err(format!("expected a height from a to z, found '{c}'")),
+    })
 }
 
-#[allow(dead_code)]
-pub fn benchmark(c: &mut criterion::Criterion) {
-    let data = parse(&crate::library::read_file("data/day12.txt"));
-    c.bench_function("Day 12 - solve:", |b| b.iter(|| solve(&data)));
-    c.bench_function("Day 12 - parse & solve:", |b| {
-        b.iter(|| {
-            let data = parse(&crate::library::read_file("data/day12.txt"));
-            solve(&data)
-        })
-    });
+pub struct Day12;
+
+impl Solution for Day12 {
+    const DAY: u32 = 12;
+    const TITLE: &'static str = "Hill Climbing Algorithm";
+
+    type Input = Grid<u8>;
+    type Part1 = i32;
+    type Part2 = i32;
+
+    fn parse(input: &RawInput) -> Result<Self::Input, InputError> {
+        parse(input)
+    }
+
+    fn part1(input: &Self::Input) -> i32 {
+        solve(input).0
+    }
+
+    fn part2(input: &Self::Input) -> i32 {
+        solve(input).1
+    }
+
+    fn solve(input: &Self::Input) -> (i32, i32) {
+        solve(input)
+    }
 }
 
 #[cfg(test)]
@@ -62,14 +79,14 @@ mod tests {
 
     #[test]
     fn part1() {
-        let res = solve(&crate::library::read_file("testdata/day12.txt").join("\n"));
+        let res = solve(&parse(&RawInput::load("testdata/day12.txt").unwrap()).unwrap());
         assert_eq!(res.0, 31);
         println!("Part 1: Expected: 31, Actual: {}", res.0);
     }
 
     #[test]
     fn part2() {
-        let res = solve(&crate::library::read_file("testdata/day12.txt").join("\n"));
+        let res = solve(&parse(&RawInput::load("testdata/day12.txt").unwrap()).unwrap());
         assert_eq!(res.1, 29);
         println!("Part 2: Expected: 29, Actual: {}", res.1);
     }
@@ -1,12 +1,13 @@
 // https://adventofcode.com/2022/day/13
 
 // Need the deserialize trait for the json parser.
+use crate::library::{InputError, RawInput, Solution};
 use serde::Deserialize;
 
 // Create an enum for the different types of data. Either a vector or a number.
 #[derive(Debug, Clone, Deserialize)]
 #[serde(untagged)] // This was buried in the docs, but it allows us to have a vector or a number in the same enum, and serde will figure out which one it is. If you don't have it, it will try to force stuff in both and fail.
-enum Data {
+pub enum Data {
     Num(u8),
     Group(Vec<Data>),
 }
@@ -40,13 +41,33 @@ impl PartialOrd for Data {
     }
 }
 
-pub fn solve(data: &[String]) -> (i32, i32) {
-    // Set the data to be a vector of serde_json::from_str::Data objects.
-    let mut data = data
-        .iter()
-        .filter(|x| !x.is_empty())
-        .map(|x| serde_json::from_str::<Data>(x).unwrap())
-        .collect::<Vec<Data>>();
+// Read the packets in pairs, one pair per block. Every packet is valid json, so serde does the actual parsing.
+pub fn parse(input: &RawInput) -> Result<Vec<Data>, InputError> {
+    let mut packets = Vec::new();
+
+    for (start, block) in input.blocks() {
+        let lines = block.lines().collect::<Vec<_>>();
+        if lines.len() != 2 {
+            return Err(InputError::at(
+                start,
+                lines[0],
+                0,
+                "expected a pair of packets",
+            ));
+        }
+        for (i, line) in lines.into_iter().enumerate() {
+            let packet = serde_json::from_str::<Data>(line).map_err(|e| {
+                InputError::at(start + i, line, e.column().saturating_sub(1), e.to_string())
+            })?;
+            packets.push(packet);
+        }
+    }
+
+    Ok(packets)
+}
+
+pub fn solve(data: &[Data]) -> (i32, i32) {
+    let mut data = data.to_vec();
 
     // Step through the data in sets of 2 lines, a and b, and compare them. If b > a, then add the index of the line to the sum and continue. Use an enumerate to get the index.
     let mut p1 = 0;
@@ -79,23 +100,31 @@ pub fn solve(data: &[String]) -> (i32, i32) {
     (p1, p2)
 }
 
-// Parser that will read in the data and return a vector of strings. If a line is empty, it will be skipped.
-#[allow(dead_code)]
-pub fn run() {
-    let res = solve(&crate::library::read_file("data/day13.txt"));
-    println!("Day 13:\nStar 1: {}\nStar 2: {}\n", res.0, res.1);
-}
+pub struct Day13;
+
+impl Solution for Day13 {
+    const DAY: u32 = 13;
+    const TITLE: &'static str = "Distress Signal";
 
-#[allow(dead_code)]
-pub fn benchmark(c: &mut criterion::Criterion) {
-    let data = parse(&crate::library::read_file("data/day13.txt"));
-    c.bench_function("Day 13 - solve:", |b| b.iter(|| solve(&data)));
-    c.bench_function("Day 13 - parse & solve:", |b| {
-        b.iter(|| {
-            let data = parse(&crate::library::read_file("data/day13.txt"));
-            solve(&data)
-        })
-    });
+    type Input = Vec<Data>;
+    type Part1 = i32;
+    type Part2 = i32;
+
+    fn parse(input: &RawInput) -> Result<Self::Input, InputError> {
+        parse(input)
+    }
+
+    fn part1(input: &Self::Input) -> i32 {
+        solve(input).0
+    }
+
+    fn part2(input: &Self::Input) -> i32 {
+        solve(input).1
+    }
+
+    fn solve(input: &Self::Input) -> (i32, i32) {
+        solve(input)
+    }
 }
 
 #[cfg(test)]
@@ -105,14 +134,14 @@ mod tests {
 
     #[test]
     fn part1() {
-        let res = solve(&crate::library::read_file("testdata/day13.txt"));
+        let res = solve(&parse(&RawInput::load("testdata/day13.txt").unwrap()).unwrap());
         assert_eq!(res.0, 13);
         println!("Part 1: Expected: 13, Actual: {}", res.0);
     }
 
     #[test]
     fn part2() {
-        let res = solve(&crate::library::read_file("testdata/day13.txt"));
+        let res = solve(&parse(&RawInput::load("testdata/day13.txt").unwrap()).unwrap());
         assert_eq!(res.1, 140);
         println!("Part 2: Expected: 140, Actual: {}", res.1);
     }
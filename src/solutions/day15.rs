@@ -1,123 +1,125 @@
 // https://adventofcode.com/2022/day/15
 
-use regex::Regex;
-
-#[derive(Debug)]
-pub struct Point {
-    x: i64,
-    y: i64,
+use crate::library::parse::{self, field};
+use crate::library::{InputError, IntervalSet, Point2, RawInput, Solution};
+use hashbrown::HashSet;
+use nom::error::context;
+use nom::sequence::tuple;
+
+pub fn solve(data: &[(Point2, Point2)]) -> (i64, i64) {
+    // The row to check for p1 and how far the distress beacon can be for p2, as given by the puzzle.
+    solve_in(data, 2_000_000, 4_000_000)
 }
 
-pub fn solve(data: &Vec<(Point, Point)>) -> (i64, i64) {
-    // Variables to check against for p1/p2.
-    let y_col = 2_000_000;
-    let max = 4_000_000;
-
-    // Closure to calculate manhattan distance between two points.
-    let manhattan = |a: &Point, b: &Point| (a.x - b.x).abs() + (a.y - b.y).abs();
-
-    // Solve part 1 by finding intervals we need.
-    let mut intervals = Vec::new();
-
-    // Iterate over all the lines in vector and use the scanner and beacon to find the intervals.
-    for (s, b) in data {
-        let d = manhattan(s, b) - (s.y - y_col).abs();
-        if d >= 0 {
-            let x1 = s.x - d;
-            let x2 = s.x + d;
-            intervals.push((x1, x2));
-        }
-    }
-
-    // Iterate over the intervals and find the points that are in any of them and add them to a set.
-    let mut set = Vec::new();
-    for (a, b) in intervals {
-        for i in a..=b {
-            set.push(i);
-        }
-    }
+// The example uses a smaller row and search area than the real input, so the tests pass their own.
+pub fn solve_in(data: &[(Point2, Point2)], y_col: i64, max: i64) -> (i64, i64) {
+    // Solve part 1 by finding the stretch of the row each sensor can see, and merging them all together.
+    let covered = data
+        .iter()
+        .filter_map(|(s, b)| {
+            let d = s.manhattan(*b) - (s.y - y_col).abs();
+            (d >= 0).then_some((s.x - d, s.x + d))
+        })
+        .collect::<IntervalSet>();
 
-    // Sort the set.
-    set.sort();
+    // Any beacons sitting in that row are covered too, but those are places a beacon can be, so take them back off.
+    let beacons = data
+        .iter()
+        .filter(|(_, b)| b.y == y_col && covered.contains(b.x))
+        .map(|(_, b)| b.x)
+        .collect::<HashSet<_>>();
 
-    let mut p1 = 0;
-
-    // Iterate over the set and find the points that are not equal to the next point.
-    for i in 0..set.len() - 1 {
-        if set[i] != set[i + 1] {
-            p1 += 1;
-        }
-    }
+    let p1 = covered.len() - beacons.len() as i64;
 
     // Create a vector of tuples that contain the (x, y, dist) coordinates of the scanner and their manhattan distance to the beacon.
     let mut vec = Vec::new();
     for (s, b) in data {
-        vec.push((s.x, s.y, manhattan(s, b)));
+        vec.push((s.x, s.y, s.manhattan(*b)));
     }
 
     // Create a hashset to store the points for the various lines.
-    let mut set_a1 = std::collections::HashSet::new(); // x - y + dist + 1.
-    let mut set_a2 = std::collections::HashSet::new(); // x - y - dist - 1.
-    let mut set_b1 = std::collections::HashSet::new(); // x + y + dist + 1.
-    let mut set_b2 = std::collections::HashSet::new(); // x + y - dist - 1.
+    let mut set_a1 = HashSet::new(); // x - y + dist + 1.
+    let mut set_a2 = HashSet::new(); // x - y - dist - 1.
+    let mut set_b1 = HashSet::new(); // x + y + dist + 1.
+    let mut set_b2 = HashSet::new(); // x + y - dist - 1.
 
-    for (x, y, dist) in vec {
+    for &(x, y, dist) in &vec {
         set_a1.insert(x - y + dist + 1);
         set_a2.insert(x - y - dist - 1);
         set_b1.insert(x + y + dist + 1);
         set_b2.insert(x + y - dist - 1);
     }
 
-    // Find intersections of sets a1 and a2 and of pair b1 and b2.
-    // It should be one number in each set. (i.e. the first and only element)
-    let a = set_a1.intersection(&set_a2).collect::<Vec<_>>()[0];
-    let b = set_b1.intersection(&set_b2).collect::<Vec<_>>()[0];
-
-    // The intersection of a and b is the point we need so we can just calculate based off that.
-    let p2 = (a + b) * max / 2 + (b - a) / 2;
+    // The beacon sits just outside the edges of sensors on both sides of it, so it's on a line from a1 and a2, and one from b1 and b2.
+    // On the real input there's only one of each, but smaller inputs can have a few, so try every crossing and keep the one no sensor sees.
+    let a_lines = set_a1.intersection(&set_a2).collect::<Vec<_>>();
+    let b_lines = set_b1.intersection(&set_b2).collect::<Vec<_>>();
+
+    let p2 = a_lines
+        .iter()
+        .flat_map(|&&a| b_lines.iter().map(move |&&b| (a, b)))
+        .filter(|(a, b)| (a + b) % 2 == 0)
+        .map(|(a, b)| Point2::new((a + b) / 2, (b - a) / 2))
+        .find(|p| {
+            (0..=max).contains(&p.x)
+                && (0..=max).contains(&p.y)
+                && vec
+                    .iter()
+                    .all(|&(x, y, dist)| Point2::new(x, y).manhattan(*p) > dist)
+        })
+        .map(|p| p.x * 4_000_000 + p.y)
+        // There's no sensible answer to give if the sensors leave no gap, so don't make one up.
+        .unwrap_or_else(|| panic!("no spot between 0 and {max} is out of reach of every sensor"));
 
     (p1, p2)
 }
 
-pub fn parse(data: &[String]) -> Vec<(Point, Point)> {
-    let re =
-        Regex::new(r"Sensor at x=(-?\d+), y=(-?\d+): closest beacon is at x=(-?\d+), y=(-?\d+)")
-            .unwrap();
+pub fn parse(input: &RawInput) -> Result<Vec<(Point2, Point2)>, InputError> {
     let mut res = Vec::new();
 
-    for s in data {
-        if let Some(caps) = re.captures(s) {
-            let sensor = Point {
-                x: caps[1].parse().unwrap(),
-                y: caps[2].parse().unwrap(),
-            };
-            let beacon = Point {
-                x: caps[3].parse().unwrap(),
-                y: caps[4].parse().unwrap(),
-            };
-            res.push((sensor, beacon));
+    for (i, s) in input.lines().enumerate() {
+        if s.is_empty() {
+            continue;
         }
+        let (x, y, bx, by) = parse::line(
+            i,
+            s,
+            context(
+                "'Sensor at x=.., y=..: closest beacon is at x=.., y=..'",
+                tuple((field("x"), field("y"), field("x"), field("y"))),
+            ),
+        )?;
+        res.push((Point2::new(x, y), Point2::new(bx, by)));
     }
 
-    res
+    Ok(res)
 }
 
-#[allow(dead_code)]
-pub fn run() {
-    let res = solve(&parse(&crate::library::read_file("data/day15.txt")));
-    println!("Day 15:\nStar 1: {}\nStar 2: {}\n", res.0, res.1);
-}
+pub struct Day15;
 
-#[allow(dead_code)]
-pub fn benchmark(c: &mut criterion::Criterion) {
-    let data = parse(&crate::library::read_file("data/day15.txt"));
-    c.bench_function("Day 15 - solve:", |b| b.iter(|| solve(&data)));
-    c.bench_function("Day 15 - parse & solve:", |b| {
-        b.iter(|| {
-            let data = parse(&crate::library::read_file("data/day15.txt"));
-            solve(&data)
-        })
-    });
+impl Solution for Day15 {
+    const DAY: u32 = 15;
+    const TITLE: &'static str = "Beacon Exclusion Zone";
+
+    type Input = Vec<(Point2, Point2)>;
+    type Part1 = i64;
+    type Part2 = i64;
+
+    fn parse(input: &RawInput) -> Result<Self::Input, InputError> {
+        parse(input)
+    }
+
+    fn part1(input: &Self::Input) -> i64 {
+        solve(input).0
+    }
+
+    fn part2(input: &Self::Input) -> i64 {
+        solve(input).1
+    }
+
+    fn solve(input: &Self::Input) -> (i64, i64) {
+        solve(input)
+    }
 }
 
 #[cfg(test)]
@@ -127,15 +129,30 @@ mod tests {
 
     #[test]
     fn part1() {
-        let res = solve(&parse(&crate::library::read_file("testdata/day15.txt")));
+        let res = solve_in(
+            &parse(&RawInput::load("testdata/day15.txt").unwrap()).unwrap(),
+            10,
+            20,
+        );
         assert_eq!(res.0, 26);
         println!("Part 1: Expected: 26, Actual: {}", res.0);
     }
 
     #[test]
     fn part2() {
-        let res = solve(&parse(&crate::library::read_file("testdata/day15.txt")));
+        let res = solve_in(
+            &parse(&RawInput::load("testdata/day15.txt").unwrap()).unwrap(),
+            10,
+            20,
+        );
         assert_eq!(res.1, 56000011);
         println!("Part 2: Expected: 56000011, Actual: {}", res.1);
     }
+
+    #[test]
+    #[should_panic(expected = "out of reach of every sensor")]
+    fn no_gap() {
+        // One sensor that sees the whole area, so there's nowhere for the beacon to be.
+        solve_in(&[(Point2::new(5, 5), Point2::new(5, 15))], 5, 5);
+    }
 }
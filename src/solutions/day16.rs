@@ -1,6 +1,13 @@
 // https://adventofcode.com/2022/day/16
 
+use crate::library::graph;
+use crate::library::parse::{self, field, list, name};
+use crate::library::{InputError, RawInput, Solution};
 use hashbrown::{HashMap, HashSet};
+use nom::branch::alt;
+use nom::bytes::complete::tag;
+use nom::error::context;
+use nom::sequence::{preceded, tuple};
 use std::hash::{Hash, Hasher};
 
 // Create a structure to hold the data for each room. The flow-rate, and the exits it has via tunnels. Since it'll be used in a HashMap, we don't need to store ID, as that'll be the key.
@@ -41,50 +48,14 @@ pub fn solve(data: &HashMap<String, Room>) -> (i32, i32) {
         .collect::<HashMap<&String, &Room>>();
 
     // Let's also create a map of the distance from every room to any other room. (Only for the good rooms.)
-    let mut distances: HashMap<(String, String), i32> = HashMap::new();
-
-    // Iterate over all possible rooms.
-    for room in data {
-        // Check if the room is in the good rooms list, if it isn't, we can skip it.
-        if !good_rooms.contains_key(room.0) {
-            continue;
-        }
-
-        let mut curr: HashSet<String> = HashSet::new();
-        let mut next: HashSet<String> = HashSet::new();
-        let mut dist: i32 = 0;
-
-        // First add the distance from the current room to itself as 0 to the distances map.
-        distances.insert((room.0.to_string(), room.0.to_string()), 0);
-        // Insert the current room into the current set.
-        curr.insert(room.0.to_string());
-
-        // Iterate while the current set of rooms is not empty.
-        while !curr.is_empty() {
-            // Increment distance as we are moving.
-            dist += 1;
-
-            // Go through each Vector in the current set.
-            for position in &curr {
-                // Iterate over all possible exits from the current room.
-                for exit in &data[position].exits {
-                    // If the tuple (room, exit) is not in the distances map, add it.
-                    // Then also insert the exit into the next set.
-                    if let hashbrown::hash_map::Entry::Vacant(e) =
-                        distances.entry((room.0.to_string(), exit.to_string()))
-                    {
-                        e.insert(dist);
-                        next.insert(exit.to_string());
-                    }
-                }
-            }
-
-            // Copy the next set into the current set.
-            curr = next.clone();
-            // Clear the next set.
-            next.clear();
-        }
-    }
+    // A BFS from each good room through the tunnels, since every tunnel takes a minute.
+    let distances: HashMap<(String, String), i32> =
+        graph::all_pairs(good_rooms.keys().map(|k| k.as_str()), |r: &&str| {
+            data[*r].exits.iter().map(String::as_str)
+        })
+        .into_iter()
+        .map(|((from, to), steps)| ((from.to_string(), to.to_string()), steps as i32))
+        .collect();
 
     // Part 1.
     // Need to find the best flow-rate after arriving at the start room (AA) and then maximising the flow-rate.
@@ -271,60 +242,68 @@ pub fn best_total_flow(
     best_flow
 }
 
-pub fn parse(data: &[String]) -> HashMap<String, Room> {
+pub fn parse(input: &RawInput) -> Result<HashMap<String, Room>, InputError> {
     // Create a hashmap to store the rooms in.
     let mut rooms: HashMap<String, Room> = HashMap::new();
 
-    for line in data {
-        let words: Vec<&str> = line.split_whitespace().collect();
-        let room_name = words[1];
-
-        let flow_rate = words[4]
-            .strip_suffix(';')
-            .unwrap()
-            .strip_prefix("rate=")
-            .unwrap()
-            .parse::<i32>()
-            .unwrap();
-
-        let mut exits = Vec::new();
-
-        for word in words[9..].iter() {
-            let mut exit = word.to_string();
-            if exit.strip_suffix(',').is_some() {
-                exit = exit.strip_suffix(',').unwrap().to_string();
-            }
-            exits.push(exit);
-        }
+    for (i, line) in input.lines().enumerate() {
+        // Valve AA has flow rate=0; tunnels lead to valves DD, II, BB
+        // A valve with only one tunnel says so in the singular.
+        let (room_name, flow_rate, exits) = parse::line(
+            i,
+            line,
+            tuple((
+                preceded(tag("Valve "), name),
+                field("rate"),
+                context(
+                    "the valves it leads to",
+                    preceded(
+                        alt((
+                            tag("; tunnels lead to valves "),
+                            tag("; tunnel leads to valve "),
+                        )),
+                        list(name),
+                    ),
+                ),
+            )),
+        )?;
 
         let room = Room {
             flow: flow_rate,
-            exits,
+            exits: exits.into_iter().map(String::from).collect(),
         };
 
         rooms.insert(room_name.to_string(), room);
     }
 
-    rooms
+    Ok(rooms)
 }
 
-#[allow(dead_code)]
-pub fn run() {
-    // let res = solve(&parse(&crate::library::read_file("data/day16.txt")));
-    // Since the solution is slow for part 2, I'll just hardcode the answer.
-    println!("Day 16:\nStar 1: {}\nStar 2: {}\n", 1559, 2191);
-}
+pub struct Day16;
 
-#[allow(dead_code)]
-pub fn benchmark(c: &mut criterion::Criterion) {
-    let data = parse(&crate::library::read_file("data/day16.txt"));
-    c.bench_function("Day 16 - solve:", |b| b.iter(|| solve(&data)));
-    c.bench_function("Day 16 - parse & solve:", |b| {
-        b.iter(|| {
-            let data = parse(&crate::library::read_file("data/day16.txt"));
-            solve(&data)
-        })
-    });
+impl Solution for Day16 {
+    const DAY: u32 = 16;
+    const TITLE: &'static str = "Proboscidea Volcanium";
+
+    type Input = HashMap<String, Room>;
+    type Part1 = i32;
+    type Part2 = i32;
+
+    fn parse(input: &RawInput) -> Result<Self::Input, InputError> {
+        parse(input)
+    }
+
+    fn part1(input: &Self::Input) -> i32 {
+        solve(input).0
+    }
+
+    fn part2(input: &Self::Input) -> i32 {
+        solve(input).1
+    }
+
+    fn solve(input: &Self::Input) -> (i32, i32) {
+        solve(input)
+    }
 }
 
 #[cfg(test)]
@@ -334,7 +313,7 @@ mod tests {
 
     #[test]
     fn part1() {
-        // let res = solve(&parse(&crate::library::read_file("testdata/day16.txt")));
+        // let res = solve(&parse(&RawInput::load("testdata/day16.txt").unwrap()).unwrap());
         let res = (1651, 1707);
         assert_eq!(res.0, 1651);
         println!("Part 1: Expected: 1651, Actual: {}", res.0);
@@ -342,7 +321,7 @@ mod tests {
 
     #[test]
     fn part2() {
-        // let res = solve(&parse(&crate::library::read_file("testdata/day16.txt")));
+        // let res = solve(&parse(&RawInput::load("testdata/day16.txt").unwrap()).unwrap());
         let res = (1651, 1707);
         assert_eq!(res.1, 1707);
         println!("Part 2: Expected: 1707, Actual: {}", res.1);
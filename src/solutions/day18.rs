@@ -1,119 +1,52 @@
 // https://adventofcode.com/2022/day/18
 
-use std::collections::HashSet;
+use crate::library::bitset::Points;
+use crate::library::parse::{self, point3};
+use crate::library::{InputError, Point3, RawInput, Solution, VoxelGrid};
+use nom::combinator::map;
 
-pub fn solve(data: &[(i32, i32, i32)]) -> (i32, i32) {
-    // Scan through the data and obtain the maximum and minimum value of any axis.
-    let min = data
-        .iter()
-        .fold((i32::MAX, i32::MAX, i32::MAX), |(x, y, z), (x1, y1, z1)| {
-            (x.min(*x1), y.min(*y1), z.min(*z1))
-        });
+// Generic over the set the grid keeps its cubes in, so a HashSet can be compared against the CubeSet it normally uses.
+pub fn solve<S: Points<Point3>>(data: &VoxelGrid<S>) -> (i32, i32) {
+    // Part 1 counts every side of a cube that isn't touching another cube, even ones facing into an air pocket.
+    let p1 = data.surface_area();
 
-    let max = data
-        .iter()
-        .fold((i32::MIN, i32::MIN, i32::MIN), |(x, y, z), (x1, y1, z1)| {
-            (x.max(*x1), y.max(*y1), z.max(*z1))
-        });
-
-    // Iterate over the data vector and sum up the number of points that are adjacent to a given point but not in the data vector.
-    let p1 = data
-        .iter()
-        .map(|point| {
-            adjacent(*point)
-                .iter()
-                .filter(|p| !data.contains(p))
-                .count()
-        })
-        .sum::<usize>();
-
-    let visible = visible(data, min.0 as usize, max.0 as usize);
-    let p2 = data
-        .iter()
-        .flat_map(|point| adjacent(*point))
-        .filter(|p| visible.contains(p))
-        .count();
+    // Part 2 only counts the sides the steam can reach, by flooding in from outside the droplet.
+    let p2 = data.exterior_surface_area();
 
     (p1 as i32, p2 as i32)
 }
 
-// Get all exposed/visible points to all cubes in a HashSet.
-pub fn visible(data: &[(i32, i32, i32)], min: usize, max: usize) -> HashSet<(i32, i32, i32)> {
-    let mut visible = HashSet::new();
-    let start_point = (0, 0, 0);
+// Each line is the position of one cube of lava, and together they make up the droplet.
+pub fn parse(input: &RawInput) -> Result<VoxelGrid, InputError> {
+    let cubes = parse::lines(input.lines(), map(point3, Point3::from))?;
+    Ok(VoxelGrid::from_points(cubes))
+}
 
-    let mut stack = Vec::new();
-    stack.push(start_point);
+pub struct Day18;
 
-    let mut visited = HashSet::new();
-    visited.insert(start_point);
+impl Solution for Day18 {
+    const DAY: u32 = 18;
+    const TITLE: &'static str = "Boiling Boulders";
 
-    while let Some(point) = stack.pop() {
-        // For each adjacent point..
-        for p in adjacent(point) {
-            // If the data contains the neighbour or the neighbour is not within the bounds of (min - 1, min - 1, min - 1) to (max + 1, max + 1, max + 1)..
-            if data.contains(&p)
-                || p.0 < min as i32 - 1
-                || p.1 < min as i32 - 1
-                || p.2 < min as i32 - 1
-                || p.0 > max as i32 + 1
-                || p.1 > max as i32 + 1
-                || p.2 > max as i32 + 1
-            {
-                continue;
-            }
+    type Input = VoxelGrid;
+    type Part1 = i32;
+    type Part2 = i32;
 
-            if visited.insert(p) {
-                stack.push(p);
-                visible.insert(p);
-            }
-        }
+    fn parse(input: &RawInput) -> Result<Self::Input, InputError> {
+        parse(input)
     }
 
-    visible
-}
-
-// Get all adjacent points to a given point.
-pub fn adjacent((x, y, z): (i32, i32, i32)) -> Vec<(i32, i32, i32)> {
-    vec![
-        (x - 1, y, z),
-        (x + 1, y, z),
-        (x, y - 1, z),
-        (x, y + 1, z),
-        (x, y, z - 1),
-        (x, y, z + 1),
-    ]
-}
-
-pub fn parse(data: &[String]) -> Vec<(i32, i32, i32)> {
-    data.iter()
-        .map(|line| {
-            let mut iter = line.split(',');
-            (
-                iter.next().unwrap().parse::<i32>().unwrap(),
-                iter.next().unwrap().parse::<i32>().unwrap(),
-                iter.next().unwrap().parse::<i32>().unwrap(),
-            )
-        })
-        .collect::<Vec<(i32, i32, i32)>>()
-}
+    fn part1(input: &Self::Input) -> i32 {
+        solve(input).0
+    }
 
-#[allow(dead_code)]
-pub fn run() {
-    let res = solve(&parse(&crate::library::read_file("data/day18.txt")));
-    println!("Day 18:\nStar 1: {}\nStar 2: {}\n", res.0, res.1);
-}
+    fn part2(input: &Self::Input) -> i32 {
+        solve(input).1
+    }
 
-#[allow(dead_code)]
-pub fn benchmark(c: &mut criterion::Criterion) {
-    let data = parse(&crate::library::read_file("data/day18.txt"));
-    c.bench_function("Day 18 - solve:", |b| b.iter(|| solve(&data)));
-    c.bench_function("Day 18 - parse & solve:", |b| {
-        b.iter(|| {
-            let data = parse(&crate::library::read_file("data/day18.txt"));
-            solve(&data)
-        })
-    });
+    fn solve(input: &Self::Input) -> (i32, i32) {
+        solve(input)
+    }
 }
 
 #[cfg(test)]
@@ -123,14 +56,14 @@ mod tests {
 
     #[test]
     fn part1() {
-        let res = solve(&parse(&crate::library::read_file("testdata/day18.txt")));
+        let res = solve(&parse(&RawInput::load("testdata/day18.txt").unwrap()).unwrap());
         assert_eq!(res.0, 64);
         println!("Part 1: Expected: 64, Actual: {}", res.0);
     }
 
     #[test]
     fn part2() {
-        let res = solve(&parse(&crate::library::read_file("testdata/day18.txt")));
+        let res = solve(&parse(&RawInput::load("testdata/day18.txt").unwrap()).unwrap());
         assert_eq!(res.1, 58);
         println!("Part 2: Expected: 58, Actual: {}", res.1);
     }
@@ -1,5 +1,8 @@
 // https://adventofcode.com/2022/day/20
 
+use crate::library::{parse_field, InputError, RawInput, Solution};
+
+#[allow(dead_code)]
 pub fn solve(data: &[i64]) -> (i64, i64) {
     let p1 = mix(data, 1, 1);
     let p2 = mix(data, 10, 811589153);
@@ -38,31 +41,36 @@ pub fn mix(data: &[i64], repetitions: i64, decrypt_key: i64) -> i64 {
         .sum::<i64>()
 }
 
-pub fn parse(data: &[String]) -> Vec<i64> {
+pub fn parse(input: &RawInput) -> Result<Vec<i64>, InputError> {
     // Read in a number per line into the output vector, numbers can be negative.
     let mut res = Vec::new();
-    for line in data {
-        res.push(line.parse::<i64>().unwrap());
+    for (i, line) in input.lines().enumerate() {
+        res.push(parse_field::<i64>(i, line, line)?);
     }
-    res
+    Ok(res)
 }
 
-#[allow(dead_code)]
-pub fn run() {
-    let res = solve(&parse(&crate::library::read_file("data/day20.txt")));
-    println!("Day 20:\nStar 1: {}\nStar 2: {}\n", res.0, res.1);
-}
+pub struct Day20;
 
-#[allow(dead_code)]
-pub fn benchmark(c: &mut criterion::Criterion) {
-    let data = parse(&crate::library::read_file("data/day20.txt"));
-    c.bench_function("Day 20 - solve:", |b| b.iter(|| solve(&data)));
-    c.bench_function("Day 20 - parse & solve:", |b| {
-        b.iter(|| {
-            let data = parse(&crate::library::read_file("data/day20.txt"));
-            solve(&data)
-        })
-    });
+impl Solution for Day20 {
+    const DAY: u32 = 20;
+    const TITLE: &'static str = "Grove Positioning System";
+
+    type Input = Vec<i64>;
+    type Part1 = i64;
+    type Part2 = i64;
+
+    fn parse(input: &RawInput) -> Result<Self::Input, InputError> {
+        parse(input)
+    }
+
+    fn part1(input: &Self::Input) -> i64 {
+        mix(input, 1, 1)
+    }
+
+    fn part2(input: &Self::Input) -> i64 {
+        mix(input, 10, 811589153)
+    }
 }
 
 #[cfg(test)]
@@ -72,14 +80,14 @@ mod tests {
 
     #[test]
     fn part1() {
-        let res = solve(&parse(&crate::library::read_file("testdata/day20.txt")));
+        let res = solve(&parse(&RawInput::load("testdata/day20.txt").unwrap()).unwrap());
         assert_eq!(res.0, 3);
         println!("Part 1: Expected: 3, Actual: {}", res.0);
     }
 
     #[test]
     fn part2() {
-        let res = solve(&parse(&crate::library::read_file("testdata/day20.txt")));
+        let res = solve(&parse(&RawInput::load("testdata/day20.txt").unwrap()).unwrap());
         assert_eq!(res.1, 1623178306);
         println!("Part 2: Expected: 1623178306, Actual: {}", res.0);
     }
@@ -1,64 +1,71 @@
 // https://adventofcode.com/2022/day/21
 
+use crate::library::num::{Number, WideInt};
+use crate::library::parse::{self, int, name};
+use crate::library::{InputError, RawInput, Solution};
+use nom::branch::alt;
+use nom::bytes::complete::tag;
+use nom::character::complete::{char, one_of};
+use nom::combinator::map;
+use nom::sequence::{separated_pair, tuple};
 use std::collections::{HashMap, VecDeque};
 
 #[derive(Debug, Clone)]
 pub struct Monkey {
     name: String,
-    value: Option<i128>,
+    value: Option<i64>,
     op: Option<char>,
     requirements: Option<(String, String)>,
 }
 
-pub fn solve(data: &VecDeque<Monkey>) -> (i128, i128) {
+pub fn solve(data: &VecDeque<Monkey>) -> (WideInt, WideInt) {
+    let zero = WideInt::from(0);
+
     // Part 1.
-    let (a, b, _) = calculate(data, None);
+    let (a, b, _) = calculate::<WideInt>(data, None);
     let p1 = a + b;
 
     // Part 2. Calculate an upper and lower-bound and then hone-in on answer.
-    let (mut lower, mut upper): (i128, i128) = (0, 0);
-
-    let (m1, m2, _) = calculate(data, Some(0));
-    let (m3, m4, _) = calculate(data, Some(100));
+    let (mut lower, mut upper) = (zero.clone(), zero.clone());
 
-    #[allow(unused_assignments)]
-    let mut increasing: bool = false;
+    let (m1, m2, _) = calculate(data, Some(WideInt::from(0)));
+    let (m3, m4, _) = calculate(data, Some(WideInt::from(100)));
 
-    if m1 - m2 >= m3 - m4 {
-        increasing = false;
-    } else {
-        increasing = true;
-    }
+    // Whether the difference between root's two sides goes up as the number we shout does.
+    let increasing = m1 - m2 < m3 - m4;
 
-    for i in 0..20 {
-        upper = i128::pow(10, i);
-        let (a, b, _) = calculate(data, Some(upper));
+    // Try each power of ten in turn as the upper bound.
+    let mut power = WideInt::from(1);
+    for _ in 0..20 {
+        upper = power;
+        power = upper.clone() * WideInt::from(10);
+        let (a, b, _) = calculate(data, Some(upper.clone()));
         if !increasing {
-            if a - b < 0 {
+            if a - b < zero {
                 break;
             } else {
-                lower = upper;
+                lower = upper.clone();
             }
-        } else if a - b > 0 {
+        } else if a - b > zero {
             break;
         } else {
-            lower = upper;
+            lower = upper.clone();
         }
     }
 
-    let mut p2: i128 = 0;
-    let mut res: i128 = -1;
+    let mut p2 = zero.clone();
+    let mut res = WideInt::from(-1);
 
-    while res != 0 {
-        p2 = lower + ((upper - lower) / 2);
-        let (a, b, _) = calculate(data, Some(p2));
-        res = a - b;
+    while res != zero {
+        p2 = lower.clone() + (upper.clone() - lower.clone()) / WideInt::from(2);
+        let (a, b, _) = calculate(data, Some(p2.clone()));
+        res = a.clone() - b.clone();
         match a.cmp(&b) {
             std::cmp::Ordering::Greater => {
                 if increasing {
-                    upper = p2;
+                    upper = p2.clone();
                 } else {
-                    lower = p2;
+                    lower = p2.clone();
                 }
             }
             std::cmp::Ordering::Equal => {
@@ -66,9 +73,9 @@ pub fn solve(data: &VecDeque<Monkey>) -> (i128, i128) {
             }
             std::cmp::Ordering::Less => {
                 if increasing {
-                    lower = p2;
+                    lower = p2.clone();
                 } else {
-                    upper = p2;
+                    upper = p2.clone();
                 }
             }
         }
@@ -79,9 +86,10 @@ pub fn solve(data: &VecDeque<Monkey>) -> (i128, i128) {
 
 // This function is designed to basically do both parts, based on if we pass a value to the human or not.
 // Part 2 is basically just an extension of part 1, but we call the function by changing the human value using a binary search.
-pub fn calculate(data: &VecDeque<Monkey>, human: Option<i128>) -> (i128, i128, bool) {
+// Generic over the number type, since a long enough chain of multiplications can outgrow even an i128.
+pub fn calculate<N: Number>(data: &VecDeque<Monkey>, human: Option<N>) -> (N, N, bool) {
     // Create a lookup HashMap which will contain the values of each given key/monkey.
-    let mut lookup: HashMap<String, i128> = HashMap::new();
+    let mut lookup: HashMap<String, N> = HashMap::new();
     let (mut left, mut right) = (String::new(), String::new());
 
     // We need to loop while the lookup table does not contain the root key..
@@ -90,8 +98,8 @@ pub fn calculate(data: &VecDeque<Monkey>, human: Option<i128>) -> (i128, i128, b
         for monkey in data.iter() {
             // Check to see if we have they current monkey's name be "humn"..
             if monkey.name == "humn" && human.is_some() {
-                if let Some(human) = human {
-                    lookup.insert("humn".to_string(), human);
+                if let Some(human) = &human {
+                    lookup.insert("humn".to_string(), human.clone());
                 }
             }
 
@@ -102,24 +110,23 @@ pub fn calculate(data: &VecDeque<Monkey>, human: Option<i128>) -> (i128, i128, b
                 right = requirements.1.to_string();
                 if lookup.contains_key(&left) && lookup.contains_key(&right) {
                     return (
-                        *lookup.get(&left).unwrap(),
-                        *lookup.get(&right).unwrap(),
-                        lookup.get(&requirements.0).unwrap()
-                            == lookup.get(&requirements.1).unwrap(),
+                        lookup[&left].clone(),
+                        lookup[&right].clone(),
+                        lookup[&requirements.0] == lookup[&requirements.1],
                     );
                 }
             }
 
             // If the name is not contained in keys... we need to do the operation..
             if !lookup.contains_key(&monkey.name) {
-                if monkey.op.is_some() {
+                if let Some(op) = monkey.op {
                     // Grab requirements..
                     let requirements = monkey.requirements.as_ref().unwrap();
                     if lookup.contains_key(&requirements.0) && lookup.contains_key(&requirements.1)
                     {
-                        let a = lookup.get(&requirements.0).unwrap();
-                        let b = lookup.get(&requirements.1).unwrap();
-                        match monkey.op.unwrap() {
+                        let a = lookup[&requirements.0].clone();
+                        let b = lookup[&requirements.1].clone();
+                        match op {
                             '+' => {
                                 lookup.insert(monkey.name.clone(), a + b);
                             }
@@ -136,74 +143,66 @@ pub fn calculate(data: &VecDeque<Monkey>, human: Option<i128>) -> (i128, i128, b
                         }
                     }
                 } else {
-                    lookup.insert(monkey.name.clone(), monkey.value.unwrap());
+                    lookup.insert(monkey.name.clone(), N::from(monkey.value.unwrap()));
                 }
             }
         }
     }
 
-    (
-        *lookup.get(&left).unwrap(),
-        *lookup.get(&right).unwrap(),
-        false,
-    )
+    (lookup[&left].clone(), lookup[&right].clone(), false)
 }
 
-pub fn parse(data: &[String]) -> VecDeque<Monkey> {
+pub fn parse(input: &RawInput) -> Result<VecDeque<Monkey>, InputError> {
     // Create a new monkey dequeue I can add/pop off and remove from specific points.
     let mut monkeys: VecDeque<Monkey> = VecDeque::new();
 
-    // Iterate through the data and add each monkeys info into the vector, depending on if it has specific amount of entries in the line or not.
-    for line in data {
-        let info = line.split_whitespace().collect::<Vec<_>>();
-
-        // Depending on how many items are in the vector, we do different things.
-        match info.len() {
-            2 => {
-                let n = info[0].trim_matches(':').to_string();
-                let v = info[1].parse::<i128>().unwrap();
-                monkeys.push_back(Monkey {
-                    name: n,
-                    value: Some(v),
-                    op: None,
-                    requirements: None,
-                });
-            }
-            4 => {
-                let n = info[0].trim_matches(':').to_string();
-                let o = info[2].chars().next().unwrap();
-                let a = info[1].to_string();
-                let b = info[3].to_string();
-                monkeys.push_back(Monkey {
-                    name: n,
-                    value: None,
-                    op: Some(o),
-                    requirements: Some((a, b)),
-                });
-            }
-            _ => {}
-        }
+    // Each line is a monkey's name and its job, which is either a number like "dbpl: 5" or an operation on two other monkeys like "root: pwmn + pppw".
+    let job = alt((
+        map(int, |v| (Some(v), None, None)),
+        map(
+            tuple((name, char(' '), one_of("+-*/"), char(' '), name)),
+            |(a, _, o, _, b)| (None, Some(o), Some((a.to_string(), b.to_string()))),
+        ),
+    ));
+    for (n, (value, op, requirements)) in
+        parse::lines(input.lines(), separated_pair(name, tag(": "), job))?
+    {
+        monkeys.push_back(Monkey {
+            name: n.to_string(),
+            value,
+            op,
+            requirements,
+        });
     }
 
-    monkeys
+    Ok(monkeys)
 }
 
-#[allow(dead_code)]
-pub fn run() {
-    let res = solve(&parse(&crate::library::read_file("data/day21.txt")));
-    println!("Day 21:\nStar 1: {}\nStar 2: {}\n", res.0, res.1);
-}
+pub struct Day21;
+
+impl Solution for Day21 {
+    const DAY: u32 = 21;
+    const TITLE: &'static str = "Monkey Math";
 
-#[allow(dead_code)]
-pub fn benchmark(c: &mut criterion::Criterion) {
-    let data = parse(&crate::library::read_file("data/day21.txt"));
-    c.bench_function("Day 21 - solve:", |b| b.iter(|| solve(&data)));
-    c.bench_function("Day 21 - parse & solve:", |b| {
-        b.iter(|| {
-            let data = parse(&crate::library::read_file("data/day21.txt"));
-            solve(&data)
-        })
-    });
+    type Input = VecDeque<Monkey>;
+    type Part1 = WideInt;
+    type Part2 = WideInt;
+
+    fn parse(input: &RawInput) -> Result<Self::Input, InputError> {
+        parse(input)
+    }
+
+    fn part1(input: &Self::Input) -> WideInt {
+        solve(input).0
+    }
+
+    fn part2(input: &Self::Input) -> WideInt {
+        solve(input).1
+    }
+
+    fn solve(input: &Self::Input) -> (WideInt, WideInt) {
+        solve(input)
+    }
 }
 
 #[cfg(test)]
@@ -213,15 +212,15 @@ mod tests {
 
     #[test]
     fn part1() {
-        let res = solve(&parse(&crate::library::read_file("testdata/day21.txt")));
-        assert_eq!(res.0, 152);
+        let res = solve(&parse(&RawInput::load("testdata/day21.txt").unwrap()).unwrap());
+        assert_eq!(res.0, WideInt::from(152));
         println!("Part 1: Expected: 152, Actual: {}", res.0);
     }
 
     #[test]
     fn part2() {
-        let res = solve(&parse(&crate::library::read_file("testdata/day21.txt")));
-        assert_eq!(res.1, 301);
-        println!("Part 1: Expected: 301, Actual: {}", res.0);
+        let res = solve(&parse(&RawInput::load("testdata/day21.txt").unwrap()).unwrap());
+        assert_eq!(res.1, WideInt::from(301));
+        println!("Part 2: Expected: 301, Actual: {}", res.1);
     }
 }
@@ -1,23 +1,20 @@
 // https://adventofcode.com/2022/day/23
 
+use crate::library::bitset::Points;
+use crate::library::{Direction, InputError, Point2, PointSet, RawInput, Solution};
 use itertools::Itertools;
-use std::collections::{HashMap, HashSet};
-
-pub fn solve(data: &mut HashSet<(i32, i32)>) -> (i32, i32) {
-    // Create an array of tuples that represent the 8 directions as offsets from the current position.
-    let directions = [
-        // Positive Y = Down (South)
-        // Positive X = Right (East)
-        (-1, -1), // Top Left
-        (0, -1),  // Top
-        (1, -1),  // Top Right
-        (-1, 0),  // Left
-        (1, 0),   // Right
-        (-1, 1),  // Bottom Left
-        (0, 1),   // Bottom
-        (1, 1),   // Bottom Right
-    ];
-
+use std::collections::HashMap;
+
+// The elves consider north, south, west and then east, starting one further along the list each round.
+const ORDER: [Direction; 4] = [
+    Direction::Up,
+    Direction::Down,
+    Direction::Left,
+    Direction::Right,
+];
+
+// Generic over the set the elves are kept in, so a HashSet can be compared against the PointSet the day normally uses.
+pub fn solve<S: Points<Point2>>(data: &mut S) -> (i32, i32) {
     let mut p1 = 0;
     let mut p2 = 0;
 
@@ -25,48 +22,28 @@ pub fn solve(data: &mut HashSet<(i32, i32)>) -> (i32, i32) {
     for t in 0.. {
         // This is a neat trick where we can infinite loop as needed, but keep track of time too.
         // Create a new hashmap to hold future elf positions.
-        let mut futures: HashMap<(i32, i32), Vec<(i32, i32)>> = HashMap::new();
-
-        for &(x, y) in data.iter() {
-            // So basically check to see if the data set contains the current position + the direction offset of any of the 8 directions.
-            // So for example if I have x, y. Then I want to check if the data set contains coordinates (x-1, y-1), (x-1, y), etc..
-            let neighbours = directions
-                .iter()
-                .map(|&(dx, dy)| (x + dx, y + dy))
-                .map(|pos| data.contains(&pos))
-                .collect_vec();
-
-            // Check to see if all neighbours are false, if so we just continue to the next iteration.
+        let mut futures: HashMap<Point2, Vec<Point2>> = HashMap::new();
+
+        for elf in data.iter() {
+            // Check to see if there are no elves in any of the 8 spots around, if so we just continue to the next iteration.
             // Since if there are no neighbours, then we don't need to do anything/elf won't move.
-            if neighbours.iter().all(|&n| !n) {
+            if elf.neighbours8().iter().all(|p| !data.contains(p)) {
                 continue;
             }
 
-            // Create an array for possible future positions, based on current neighbours present.
-            let possibilities = [
-                (
-                    !neighbours[1] && !neighbours[2] && !neighbours[0],
-                    (x, y - 1),
-                ), // If no elf on N, NE or NW, move to N.
-                (
-                    !neighbours[6] && !neighbours[7] && !neighbours[5],
-                    (x, y + 1),
-                ), // No elf S, SE or SW, move to S.
-                (
-                    !neighbours[0] && !neighbours[5] && !neighbours[3],
-                    (x - 1, y),
-                ), // No elf W, NW or SW, move to W.
-                (
-                    !neighbours[2] && !neighbours[7] && !neighbours[4],
-                    (x + 1, y),
-                ), // No elf E, NE or SE, move to E.
-            ];
-
             for i in 0..4 {
-                // Get the current possibility, make sure we account for where in the possibilities array to start, since it rotates.
-                let (can_move, pos) = possibilities[(i + t) % 4];
-                if can_move {
-                    futures.entry(pos).or_default().push((x, y));
+                // Get the current direction, make sure we account for where in the order to start, since it rotates.
+                let dir = ORDER[(i + t) % 4];
+                let ahead = elf.step(dir);
+
+                // If there's no elf ahead, or diagonally ahead on either side, move that way.
+                let side = [
+                    ahead,
+                    ahead.step(dir.turn_left()),
+                    ahead.step(dir.turn_right()),
+                ];
+                if side.iter().all(|p| !data.contains(p)) {
+                    futures.entry(ahead).or_default().push(elf);
                     break;
                 }
             }
@@ -96,23 +73,24 @@ pub fn solve(data: &mut HashSet<(i32, i32)>) -> (i32, i32) {
         // If the time is 9, then we cna solve part 1.
         if t == 9 {
             // Calculate the minimum and maximum x and y coordinates.
-            let (min_x, max_x) = data.iter().map(|&(x, _)| x).minmax().into_option().unwrap();
-            let (min_y, max_y) = data.iter().map(|&(_, y)| y).minmax().into_option().unwrap();
-            p1 = (1 + max_x - min_x) * (1 + max_y - min_y) - data.len() as i32;
+            let (min_x, max_x) = data.iter().map(|p| p.x).minmax().into_option().unwrap();
+            let (min_y, max_y) = data.iter().map(|p| p.y).minmax().into_option().unwrap();
+            p1 = ((1 + max_x - min_x) * (1 + max_y - min_y)) as i32 - data.len() as i32;
         }
     }
 
     (p1, p2)
 }
 
-pub fn parse(data: &[String]) -> HashSet<(i32, i32)> {
-    // Create a HashSet of the data by iterating over the lines and for each location, get the x and y coordinates and put them in a tuple if the character is a '#'.
-    data.iter()
+pub fn parse(input: &RawInput) -> PointSet {
+    // Create a set of the data by iterating over the lines and for each location, get the x and y coordinates and put them in a tuple if the character is a '#'.
+    input
+        .lines()
         .enumerate()
         .flat_map(|(y, line)| {
             line.chars().enumerate().filter_map(move |(x, c)| {
                 if c == '#' {
-                    Some((x as i32, y as i32))
+                    Some(Point2::new(x as i64, y as i64))
                 } else {
                     None
                 }
@@ -121,22 +99,31 @@ pub fn parse(data: &[String]) -> HashSet<(i32, i32)> {
         .collect()
 }
 
-#[allow(dead_code)]
-pub fn run() {
-    let res = solve(&mut parse(&crate::library::read_file("data/day23.txt")));
-    println!("Day 23:\nStar 1: {}\nStar 2: {}\n", res.0, res.1);
-}
+pub struct Day23;
 
-#[allow(dead_code)]
-pub fn benchmark(c: &mut criterion::Criterion) {
-    let data = parse(&crate::library::read_file("data/day23.txt"));
-    c.bench_function("Day 23 - solve:", |b| b.iter(|| solve(&data)));
-    c.bench_function("Day 23 - parse & solve:", |b| {
-        b.iter(|| {
-            let data = parse(&crate::library::read_file("data/day23.txt"));
-            solve(&data)
-        })
-    });
+impl Solution for Day23 {
+    const DAY: u32 = 23;
+    const TITLE: &'static str = "Unstable Diffusion";
+
+    type Input = PointSet;
+    type Part1 = i32;
+    type Part2 = i32;
+
+    fn parse(input: &RawInput) -> Result<Self::Input, InputError> {
+        Ok(parse(input))
+    }
+
+    fn part1(input: &Self::Input) -> i32 {
+        solve(&mut input.clone()).0
+    }
+
+    fn part2(input: &Self::Input) -> i32 {
+        solve(&mut input.clone()).1
+    }
+
+    fn solve(input: &Self::Input) -> (i32, i32) {
+        solve(&mut input.clone())
+    }
 }
 
 #[cfg(test)]
@@ -146,14 +133,14 @@ mod tests {
 
     #[test]
     fn part1() {
-        let res = solve(&mut parse(&crate::library::read_file("testdata/day23.txt")));
+        let res = solve(&mut parse(&RawInput::load("testdata/day23.txt").unwrap()));
         assert_eq!(res.0, 110);
         println!("Part 1: Expected: 110, Actual: {}", res.0);
     }
 
     #[test]
     fn part2() {
-        let res = solve(&mut parse(&crate::library::read_file("testdata/day23.txt")));
+        let res = solve(&mut parse(&RawInput::load("testdata/day23.txt").unwrap()));
         assert_eq!(res.1, 20);
         println!("Part 2: Expected: 20, Actual: {}", res.1);
     }
@@ -1,41 +1,120 @@
 // https://adventofcode.com/2022/day/24
 
-#[allow(unused_imports)]
-use hashbrown::HashSet;
-#[allow(unused_imports)]
-use std::collections::VecDeque;
-
-pub fn solve(data: &Vec<Vec<char>>) -> (i32, i32) {
-    // This day was done in Python, code to be ported.
-    (0, 0)
+use crate::library::graph;
+use crate::library::{Grid, InputError, RawInput, Solution};
+
+pub fn solve(data: &Grid<char>) -> (i32, i32) {
+    // The way in is the gap in the top wall, and the way out is the gap in the bottom wall.
+    let gap = |y: usize| {
+        let x = data.row(y).iter().position(|&c| c == '.').unwrap();
+        (x as i32, y as i32)
+    };
+    let start = gap(0);
+    let end = gap(data.height() - 1);
+
+    // Part 1 is a single trip across, part 2 goes back for the snacks and then across again.
+    let there = calculate(data, 0, start, end);
+    let back = calculate(data, there, end, start);
+    let again = calculate(data, back, start, end);
+
+    (there, again)
 }
 
 // Simply use a BFS to find the shortest path between the start and end points.
 // Since the blizzards are periodic, we will know exactly if there is a blizzard at any given point or not.
-pub fn calculate(_data: &[Vec<char>], _timestep: i32, _start: (i32, i32), _end: (i32, i32)) -> i32 {
-    0
+// Each state is a position and the time modulo the blizzards' period, so the search space stays finite.
+// Returns the time we arrive at the end, having set off from the start at `timestep`.
+pub fn calculate(data: &Grid<char>, timestep: i32, start: (i32, i32), end: (i32, i32)) -> i32 {
+    // The size of the valley inside the walls, which is what the blizzards wrap around.
+    let width = data.width() as i32 - 2;
+    let height = data.height() as i32 - 2;
+
+    // Every blizzard is back where it started after both the width and height have come round.
+    let period = lcm(width, height);
+
+    // A cell is clear at a time unless a blizzard has blown into it. Rather than moving every blizzard,
+    // look back along each row and column to where a blizzard heading this way would have had to start.
+    let clear = |(x, y): (i32, i32), t: i32| {
+        if x < 0 || y < 0 || x > width + 1 || y > height + 1 {
+            return false;
+        }
+        if data[(x as usize, y as usize)] == '#' {
+            return false;
+        }
+        // The gaps in the walls are outside the valley, so no blizzards ever reach them.
+        if y == 0 || y == height + 1 {
+            return true;
+        }
+        let (ix, iy) = (x - 1, y - 1);
+        let at = |x: i32, y: i32| data[(x as usize + 1, y as usize + 1)];
+        at((ix - t).rem_euclid(width), iy) != '>'
+            && at((ix + t).rem_euclid(width), iy) != '<'
+            && at(ix, (iy - t).rem_euclid(height)) != 'v'
+            && at(ix, (iy + t).rem_euclid(height)) != '^'
+    };
+
+    // Each minute we can wait where we are, or move one step in any direction.
+    let path = graph::bfs(
+        (start, timestep % period),
+        |&((x, y), t)| {
+            let next = (t + 1) % period;
+            [(0, 0), (0, -1), (1, 0), (0, 1), (-1, 0)]
+                .into_iter()
+                .map(move |(dx, dy)| (x + dx, y + dy))
+                .filter(move |&p| clear(p, next))
+                .map(move |p| (p, next))
+        },
+        |&(p, _)| p == end,
+    )
+    .unwrap();
+
+    timestep + path.cost as i32
 }
 
-pub fn parse(data: &[String]) -> Vec<Vec<char>> {
-    data.iter().map(|x| x.chars().collect()).collect()
+// Lowest common multiple, via Euclid's algorithm for the greatest common divisor.
+fn lcm(a: i32, b: i32) -> i32 {
+    let (mut x, mut y) = (a, b);
+    while y != 0 {
+        (x, y) = (y, x % y);
+    }
+    a / x * b
 }
 
-#[allow(dead_code)]
-pub fn run() {
-    let res = solve(&parse(&crate::library::read_file("data/day24.txt")));
-    println!("Day 24:\nStar 1: {}\nStar 2: {}\n", res.0, res.1);
+// The valley's walls, open ground and blizzards, as they are at the start.
+pub fn parse(input: &RawInput) -> Result<Grid<char>, InputError> {
+    Grid::parse(input.lines(), |c| match c {
+        '#' | '.' | '>' | '<' | '^' | 'v' => Ok(c),
+        _ => Err(format!(
+            "expected a wall, ground or a blizzard, found '{c}'"
+        )),
+    })
 }
 
-#[allow(dead_code)]
-pub fn benchmark(c: &mut criterion::Criterion) {
-    let data = parse(&crate::library::read_file("data/day24.txt"));
-    c.bench_function("Day 24 - solve:", |b| b.iter(|| solve(&data)));
-    c.bench_function("Day 24 - parse & solve:", |b| {
-        b.iter(|| {
-            let data = parse(&crate::library::read_file("data/day24.txt"));
-            solve(&data)
-        })
-    });
+pub struct Day24;
+
+impl Solution for Day24 {
+    const DAY: u32 = 24;
+    const TITLE: &'static str = "Blizzard Basin";
+
+    type Input = Grid<char>;
+    type Part1 = i32;
+    type Part2 = i32;
+
+    fn parse(input: &RawInput) -> Result<Self::Input, InputError> {
+        parse(input)
+    }
+
+    fn part1(input: &Self::Input) -> i32 {
+        solve(input).0
+    }
+
+    fn part2(input: &Self::Input) -> i32 {
+        solve(input).1
+    }
+
+    fn solve(input: &Self::Input) -> (i32, i32) {
+        solve(input)
+    }
 }
 
 #[cfg(test)]
@@ -45,15 +124,15 @@ mod tests {
 
     #[test]
     fn part1() {
-        let res = solve(&parse(&crate::library::read_file("testdata/day24.txt")));
+        let res = solve(&parse(&RawInput::load("testdata/day24.txt").unwrap()).unwrap());
         assert_eq!(res.0, 18);
         println!("Part 1: Expected: 18, Actual: {}", res.0);
     }
 
     #[test]
     fn part2() {
-        let res = solve(&parse(&crate::library::read_file("testdata/day24.txt")));
-        assert_eq!(res.1, 0);
-        println!("Part 2: Expected: 0, Actual: {}", res.1);
+        let res = solve(&parse(&RawInput::load("testdata/day24.txt").unwrap()).unwrap());
+        assert_eq!(res.1, 54);
+        println!("Part 2: Expected: 54, Actual: {}", res.1);
     }
 }
@@ -1,55 +1,85 @@
 // https://adventofcode.com/2022/day/25
 
-pub fn solve(data: &[String]) -> (String, String) {
+use crate::library::num::{Int, Number};
+use crate::library::{InputError, RawInput, Solution};
+
+pub fn solve(data: &[Vec<i64>]) -> (String, String) {
     // Solve p1 by converting the SNAFU to a decimal number, summing them, and converting the sum back to SNAFU.
-    let sum = data.iter().map(|s| snafu_to_decimal(s)).sum::<i64>();
+    let sum = data
+        .iter()
+        .map(|s| snafu_to_decimal::<Int>(s))
+        .fold(Int::from(0), |a, b| a + b);
     // Use the recursive function to create the SNAFU for the sum.
     (decimal_to_snafu(sum), "Congratulations!".to_string())
 }
 
-fn snafu_to_decimal(s: &str) -> i64 {
-    s.chars().fold(0, |n, d| {
+// Generic over the number type, as enough long SNAFU numbers add up to more than an i64 can hold.
+fn snafu_to_decimal<N: Number>(digits: &[i64]) -> N {
+    digits.iter().fold(N::from(0), |n, &d| {
         // https://doc.rust-lang.org/stable/std/iter/trait.Iterator.html#method.fold
-        n * 5
-            + match d {
-                '2' => 2,
-                '1' => 1,
-                '0' => 0,
-                '-' => -1,
-                '=' => -2,
-                _ => unreachable!(), // https://doc.rust-lang.org/std/macro.unreachable.html
-            }
+        n * N::from(5) + N::from(d)
     })
 }
 
-fn decimal_to_snafu(n: i64) -> String {
-    if n == 0 {
+fn decimal_to_snafu<N: Number>(n: N) -> String {
+    if n == N::from(0) {
         "".to_string()
     } else {
-        decimal_to_snafu((n + 2) / 5) + ["0", "1", "2", "=", "-"][n as usize % 5]
+        let digit = (n.clone() % N::from(5)).to_i64().unwrap() as usize;
+        decimal_to_snafu((n + N::from(2)) / N::from(5)) + ["0", "1", "2", "=", "-"][digit]
     }
 }
 
-pub fn parse(data: &[String]) -> Vec<String> {
-    data.iter().map(|s| s.to_string()).collect()
+// Each SNAFU number is read into its digits, most significant first, so a stray character is caught here rather than while solving.
+pub fn parse(input: &RawInput) -> Result<Vec<Vec<i64>>, InputError> {
+    input
+        .lines()
+        .enumerate()
+        .map(|(i, line)| {
+            line.char_indices()
+                .map(|(offset, d)| match d {
+                    '2' => Ok(2),
+                    '1' => Ok(1),
+                    '0' => Ok(0),
+                    '-' => Ok(-1),
+                    '=' => Ok(-2),
+                    _ => Err(InputError::at(
+                        i,
+                        line,
+                        offset,
+                        format!("invalid SNAFU digit '{d}'"),
+                    )),
+                })
+                .collect()
+        })
+        .collect()
 }
 
-#[allow(dead_code)]
-pub fn run() {
-    let res = solve(&parse(&crate::library::read_file("data/day25.txt")));
-    println!("Day 25:\nStar 1: {}\nStar 2: {}\n", res.0, res.1);
-}
+pub struct Day25;
 
-#[allow(dead_code)]
-pub fn benchmark(c: &mut criterion::Criterion) {
-    let data = parse(&crate::library::read_file("data/day25.txt"));
-    c.bench_function("Day 25 - solve:", |b| b.iter(|| solve(&data)));
-    c.bench_function("Day 25 - parse & solve:", |b| {
-        b.iter(|| {
-            let data = parse(&crate::library::read_file("data/day25.txt"));
-            solve(&data)
-        })
-    });
+impl Solution for Day25 {
+    const DAY: u32 = 25;
+    const TITLE: &'static str = "Full of Hot Air";
+
+    type Input = Vec<Vec<i64>>;
+    type Part1 = String;
+    type Part2 = String;
+
+    fn parse(input: &RawInput) -> Result<Self::Input, InputError> {
+        parse(input)
+    }
+
+    fn part1(input: &Self::Input) -> String {
+        solve(input).0
+    }
+
+    fn part2(input: &Self::Input) -> String {
+        solve(input).1
+    }
+
+    fn solve(input: &Self::Input) -> (String, String) {
+        solve(input)
+    }
 }
 
 #[cfg(test)]
@@ -59,14 +89,14 @@ mod tests {
 
     #[test]
     fn part1() {
-        let res = solve(&parse(&crate::library::read_file("testdata/day25.txt")));
+        let res = solve(&parse(&RawInput::load("testdata/day25.txt").unwrap()).unwrap());
         assert_eq!(res.0, "2=-1=0");
         println!("Part 1: Expected: 2=-1=0, Actual: {}", res.0);
     }
 
     #[test]
     fn part2() {
-        let res = solve(&parse(&crate::library::read_file("testdata/day25.txt")));
+        let res = solve(&parse(&RawInput::load("testdata/day25.txt").unwrap()).unwrap());
         assert_eq!(res.1, "Congratulations!");
         println!("Part 2: Expected: Congratulations!, Actual: {}", res.1);
     }
@@ -1,2 +1,58 @@
 // Auto import using dirmod all.
 dirmod::all!(default pub);
+
+use crate::library::Day;
+
+// Every solved day, in calendar order. Adding a day only needs a new entry here.
+pub static SOLUTIONS: &[&dyn Day] = &[
+    &day01::Day01,
+    &day02::Day02,
+    &day03::Day03,
+    &day04::Day04,
+    &day05::Day05,
+    &day06::Day06,
+    &day07::Day07,
+    &day08::Day08,
+    &day09::Day09,
+    &day10::Day10,
+    &day11::Day11,
+    &day12::Day12,
+    &day13::Day13,
+    &day14::Day14,
+    &day15::Day15,
+    &day16::Day16,
+    &day17::Day17,
+    &day18::Day18,
+    &day19::Day19,
+    &day20::Day20,
+    &day21::Day21,
+    &day22::Day22,
+    &day23::Day23,
+    &day24::Day24,
+    &day25::Day25,
+];
+
+// Look up a registered day by its number.
+pub fn find(day: u32) -> Option<&'static dyn Day> {
+    SOLUTIONS.iter().copied().find(|d| d.number() == day)
+}
+
+#[cfg(test)]
+mod tests {
+    #[allow(unused_imports)]
+    use super::*;
+
+    #[test]
+    fn registry_is_in_order() {
+        let days = SOLUTIONS.iter().map(|d| d.number()).collect::<Vec<u32>>();
+        assert_eq!(days, (1..=25).collect::<Vec<u32>>());
+    }
+
+    #[test]
+    fn registry_has_testdata() {
+        for day in SOLUTIONS {
+            let path = format!("testdata/day{:02}.txt", day.number());
+            assert!(std::path::Path::new(&path).exists(), "Missing {path}");
+        }
+    }
+}
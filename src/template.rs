@@ -1,23 +1,40 @@
 // https://adventofcode.com/2022/day/XX
 
-pub fn solve(data: &[String]) -> (i32, i32) {
+use crate::library::{InputError, RawInput, Solution};
+
+pub fn solve(_data: &[String]) -> (i32, i32) {
     (0, 0)
 }
 
-pub fn parse(data: &[String]) -> Vec<String> {
-    data.iter().map(|s| s.to_string()).collect()
+pub fn parse(input: &RawInput) -> Result<Vec<String>, InputError> {
+    Ok(input.lines().map(String::from).collect())
 }
 
-#[allow(dead_code)]
-pub fn run() {
-    let res = solve(&parse(&crate::library::read_file("data/dayXX.txt")));
-    println!("Day XX:\nStar 1: {}\nStar 2: {}\n", res.0, res.1);
-}
+pub struct DayXX;
+
+impl Solution for DayXX {
+    const DAY: u32 = XX;
+    const TITLE: &'static str = "";
+
+    type Input = Vec<String>;
+    type Part1 = i32;
+    type Part2 = i32;
+
+    fn parse(input: &RawInput) -> Result<Self::Input, InputError> {
+        parse(input)
+    }
 
-#[allow(dead_code)]
-pub fn benchmark(c: &mut criterion::Criterion) {
-    let data = parse(&crate::library::read_file("data/dayXX.txt"));
-    c.bench_function("Day XX", |b| b.iter(|| solve(&data)));
+    fn part1(input: &Self::Input) -> i32 {
+        solve(input).0
+    }
+
+    fn part2(input: &Self::Input) -> i32 {
+        solve(input).1
+    }
+
+    fn solve(input: &Self::Input) -> (i32, i32) {
+        solve(input)
+    }
 }
 
 #[cfg(test)]
@@ -26,15 +43,17 @@ mod tests {
     use super::*;
 
     #[test]
+    #[ignore = "part not implemented"]
     fn part1() {
-        let res = solve(&parse(&crate::library::read_file("testdata/dayXX.txt")));
+        let res = solve(&parse(&RawInput::load("testdata/dayXX.txt").unwrap()).unwrap());
         assert_eq!(res.0, 0);
         println!("Part 1: Expected: 0, Actual: {}", res.0);
     }
 
     #[test]
+    #[ignore = "part not implemented"]
     fn part2() {
-        let res = solve(&parse(&crate::library::read_file("testdata/dayXX.txt")));
+        let res = solve(&parse(&RawInput::load("testdata/dayXX.txt").unwrap()).unwrap());
         assert_eq!(res.1, 0);
         println!("Part 2: Expected: 0, Actual: {}", res.1);
     }
@@ -0,0 +1,223 @@
+// Checks every day's answers against the confirmed ones in the answer store, so a refactor that changes an answer shows up straight away.
+
+use crate::cli::Options;
+use crate::library::{self, Answer};
+use crate::runner::{self, Failure};
+use crate::solutions;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::Path;
+
+// The answers belong to the inputs they came from, so they're kept next to them, in data/answers.json for the real inputs.
+pub const ANSWERS: &str = "answers.json";
+
+// The confirmed answers for one day. A part is left out until it has been recorded.
+#[derive(Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Known {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub part1: Option<Answer>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub part2: Option<Answer>,
+}
+
+// Confirmed answers keyed by day.
+pub type Store = BTreeMap<u32, Known>;
+
+// How a part's answer compares with the confirmed one.
+#[derive(Debug, PartialEq, Eq)]
+pub enum Status {
+    Match,
+    Mismatch { expected: Answer, actual: Answer },
+    // There's no confirmed answer to compare with yet. This fails too, since nothing was actually checked.
+    Missing(Answer),
+    // The day panicked or had no input, so there's no answer at all.
+    Failed(Failure),
+}
+
+impl Status {
+    fn name(&self) -> &'static str {
+        match self {
+            Status::Match => "match",
+            Status::Mismatch { .. } => "mismatch",
+            Status::Missing(_) => "missing",
+            Status::Failed(failure) => failure.name(),
+        }
+    }
+
+    fn detail(&self) -> String {
+        match self {
+            Status::Match => String::new(),
+            Status::Mismatch { expected, actual } => format!(
+                "expected {}, got {}",
+                runner::cell(&Some(expected.clone())),
+                runner::cell(&Some(actual.clone()))
+            ),
+            Status::Missing(actual) => format!("got {}", runner::cell(&Some(actual.clone()))),
+            Status::Failed(Failure::MissingInput) => String::new(),
+            Status::Failed(Failure::BadInput(error)) => error.to_string(),
+            Status::Failed(Failure::Panicked(message)) => message.clone(),
+            Status::Failed(Failure::TimedOut(limit)) => {
+                format!("after {}", runner::format_duration(*limit))
+            }
+        }
+    }
+}
+
+// Load the answer store. A store that doesn't exist yet is just empty.
+pub fn load(path: &str) -> Result<Store, String> {
+    if !Path::new(path).exists() {
+        return Ok(Store::new());
+    }
+    let text = fs::read_to_string(path).map_err(|e| format!("Could not read {path}: {e}"))?;
+    serde_json::from_str(&text).map_err(|e| format!("Could not parse {path}: {e}"))
+}
+
+pub fn save(path: &str, store: &Store) -> Result<(), String> {
+    let text = serde_json::to_string_pretty(store).unwrap();
+    fs::write(path, text + "\n").map_err(|e| format!("Could not write {path}: {e}"))
+}
+
+// Compare what a part produced with its confirmed answer.
+pub fn status(expected: Option<&Answer>, actual: Result<&Answer, &Failure>) -> Status {
+    match (expected, actual) {
+        (_, Err(failure)) => Status::Failed(failure.clone()),
+        (None, Ok(actual)) => Status::Missing(actual.clone()),
+        (Some(expected), Ok(actual)) if expected == actual => Status::Match,
+        (Some(expected), Ok(actual)) => Status::Mismatch {
+            expected: expected.clone(),
+            actual: actual.clone(),
+        },
+    }
+}
+
+// Run the selected days against their inputs in `data` and report how each part compares with the store there.
+// With `record`, the current answers are saved as the confirmed ones instead of failing on them.
+// Returns whether everything passed, which takes every part having a confirmed answer that still matches.
+pub fn verify(options: &Options, data: &str, record: bool) -> Result<bool, String> {
+    let answers = Path::new(data).join(ANSWERS).to_string_lossy().into_owned();
+    let mut store = load(&answers)?;
+    let mut rows = Vec::new();
+    let mut counts = BTreeMap::new();
+
+    for day in options.days.iter().filter_map(|&d| solutions::find(d)) {
+        let path = library::input_path_in(data, day.number());
+        let run = runner::try_run(day, &path, options.part, options.timeout);
+        let known = store.entry(day.number()).or_default();
+
+        for &number in options.part.numbers() {
+            let expected = match number {
+                1 => &mut known.part1,
+                _ => &mut known.part2,
+            };
+            let actual = match &run.failure {
+                Some(failure) => Err(failure.clone()),
+                None => Ok(run
+                    .answer(number)
+                    .cloned()
+                    .expect("selected parts are always solved")),
+            };
+            let status = status(expected.as_ref(), actual.as_ref());
+
+            if record {
+                if let Ok(answer) = actual {
+                    *expected = Some(answer);
+                }
+            }
+
+            *counts.entry(status.name()).or_insert(0) += 1;
+            rows.push(vec![
+                format!("{:02}", day.number()),
+                number.to_string(),
+                status.name().to_string(),
+                status.detail(),
+            ]);
+        }
+    }
+
+    runner::print_aligned(&["Day", "Part", "Status", "Detail"], &rows, &[]);
+    println!();
+
+    let count = |name| counts.get(name).copied().unwrap_or(0);
+    let failed = Failure::NAMES
+        .iter()
+        .map(|&name| count(name))
+        .sum::<usize>();
+    let failures = Failure::NAMES
+        .iter()
+        .map(|&name| format!("{} {name}", count(name)))
+        .collect::<Vec<_>>();
+    println!(
+        "{} matched, {} mismatched, {} missing, {}",
+        count("match"),
+        count("mismatch"),
+        count("missing"),
+        failures.join(", ")
+    );
+
+    if record {
+        // Don't leave empty entries behind for days that failed before ever being recorded.
+        store.retain(|_, known| *known != Known::default());
+        save(&answers, &store)?;
+        println!("Recorded answers in {answers}");
+        return Ok(failed == 0);
+    }
+
+    if count("missing") > 0 {
+        println!("Run with --record to save the missing answers once they're confirmed");
+    }
+    Ok(count("mismatch") == 0 && count("missing") == 0 && failed == 0)
+}
+
+#[cfg(test)]
+mod tests {
+    #[allow(unused_imports)]
+    use super::*;
+
+    #[test]
+    fn statuses() {
+        let answer = Answer::from(24000);
+        assert_eq!(
+            status(Some(&answer), Ok(&Answer::Int(24000))),
+            Status::Match
+        );
+        assert_eq!(status(None, Ok(&answer)), Status::Missing(answer.clone()));
+        assert_eq!(
+            status(Some(&answer), Ok(&Answer::from("CMZ"))),
+            Status::Mismatch {
+                expected: answer.clone(),
+                actual: Answer::from("CMZ")
+            }
+        );
+        assert_eq!(
+            status(Some(&answer), Err(&Failure::Panicked("oops".to_string()))),
+            Status::Failed(Failure::Panicked("oops".to_string()))
+        );
+    }
+
+    #[test]
+    fn store_round_trip() {
+        let mut store = Store::new();
+        store.insert(
+            5,
+            Known {
+                part1: Some(Answer::from("CMZ")),
+                part2: None,
+            },
+        );
+        let json = serde_json::to_string(&store).unwrap();
+        assert_eq!(json, r#"{"5":{"part1":{"kind":"text","value":"CMZ"}}}"#);
+        assert_eq!(serde_json::from_str::<Store>(&json).unwrap(), store);
+    }
+
+    #[test]
+    fn examples_are_recorded() {
+        // Every registered day has both of its example answers confirmed, so `verify --data testdata` checks them all.
+        let store = load(&format!("testdata/{ANSWERS}")).unwrap();
+        for day in solutions::SOLUTIONS {
+            let known = &store[&day.number()];
+            assert!(known.part1.is_some() && known.part2.is_some());
+        }
+        assert_eq!(store[&1].part1, Some(Answer::Int(24000)));
+    }
+}